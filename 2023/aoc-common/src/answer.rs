@@ -0,0 +1,165 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use crate::AocError;
+
+// What a solution hands back. Numbers stay numbers so the runner and tests
+// compare values, not strings. Text is for the puzzles that spell their
+// answer out.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl Answer {
+    fn integer(&self) -> Option<i128> {
+        match self {
+            Answer::Unsigned(value) => Some(i128::from(*value)),
+            Answer::Signed(value) => Some(i128::from(*value)),
+            Answer::Text(_) => None,
+        }
+    }
+
+    // A number when it looks like one, text otherwise. This is how answers
+    // stored as text (golden files, the command line) come back in.
+    pub fn parse(text: &str) -> Answer {
+        let text = text.trim();
+        text.parse::<u64>()
+            .map(Answer::Unsigned)
+            .or_else(|_| text.parse::<i64>().map(Answer::Signed))
+            .unwrap_or_else(|_| Answer::Text(text.to_string()))
+    }
+}
+
+// Signed(5) and Unsigned(5) are the same answer
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => self.integer().is_some() && self.integer() == other.integer(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(Answer::parse(text))
+    }
+}
+
+macro_rules! from_integer {
+    ($variant:ident, $($int:ty),+) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Self {
+                    Answer::$variant(value as _)
+                }
+            }
+        )+
+    };
+}
+
+from_integer!(Unsigned, u8, u16, u32, u64, usize);
+from_integer!(Signed, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+// Adds the values up as u64s. Going past u64::MAX is an error instead of a
+// wrapped (or, in debug builds, panicking) total.
+pub fn checked_sum<I>(values: I) -> Result<u64, AocError>
+where
+    I: IntoIterator,
+    I::Item: Into<u64>,
+{
+    values
+        .into_iter()
+        .try_fold(0u64, |total, value| total.checked_add(value.into()))
+        .ok_or(AocError::Overflow)
+}
+
+// Same for multiplying, starting from 1
+pub fn checked_product<I>(values: I) -> Result<u64, AocError>
+where
+    I: IntoIterator,
+    I::Item: Into<u64>,
+{
+    values
+        .into_iter()
+        .try_fold(1u64, |product, value| product.checked_mul(value.into()))
+        .ok_or(AocError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("142", Answer::Unsigned(142))]
+    #[case("-3", Answer::Signed(-3))]
+    #[case(" 7\n", Answer::Unsigned(7))]
+    #[case("JXGKEAPZ", Answer::Text("JXGKEAPZ".to_string()))]
+    fn parses_numbers_before_text(#[case] text: &str, #[case] expected: Answer) {
+        assert_eq!(Answer::parse(text), expected);
+    }
+
+    #[test]
+    fn integers_compare_by_value() {
+        assert_eq!(Answer::Signed(5), Answer::Unsigned(5));
+        assert_eq!(Answer::from(5u32), Answer::from(5i64));
+        assert_ne!(Answer::Signed(-1), Answer::Unsigned(u64::MAX));
+        assert_ne!(Answer::from("5"), Answer::Unsigned(5));
+    }
+
+    #[test]
+    fn displays_like_the_value() {
+        assert_eq!(Answer::from(281u32).to_string(), "281");
+        assert_eq!(Answer::from(-4i32).to_string(), "-4");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
+
+    #[test]
+    fn sums_are_checked() {
+        assert_eq!(checked_sum([1u32, 2, 3]).unwrap(), 6);
+        assert!(matches!(
+            checked_sum([u64::MAX, 1]),
+            Err(AocError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn products_are_checked() {
+        assert_eq!(checked_product([2u32, 3, 7]).unwrap(), 42);
+        assert_eq!(checked_product(Vec::<u64>::new()).unwrap(), 1);
+        assert!(matches!(
+            checked_product([u32::MAX, u32::MAX, 2]),
+            Err(AocError::Overflow)
+        ));
+    }
+}
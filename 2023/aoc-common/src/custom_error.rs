@@ -0,0 +1,149 @@
+use miette::{Diagnostic, SourceSpan};
+use nom::Offset;
+use thiserror::Error;
+
+use crate::report::InputReport;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("failed to parse input, expected {expected}")]
+    #[diagnostic(code(aoc::parse_error))]
+    Parse {
+        #[source_code]
+        src: String,
+        #[label("expected {expected}")]
+        span: SourceSpan,
+        expected: String,
+    },
+
+    #[error("parsing stopped at line {line}, column {column}")]
+    #[diagnostic(
+        code(aoc::trailing_input),
+        help("the rest of the input was never read, so any answer would be for part of it")
+    )]
+    TrailingInput {
+        #[source_code]
+        src: String,
+        #[label("not parsed")]
+        span: SourceSpan,
+        line: usize,
+        column: usize,
+    },
+
+    #[error("the answer is too big for a u64")]
+    #[diagnostic(code(aoc::overflow))]
+    Overflow,
+
+    #[error("{0}")]
+    #[diagnostic(code(aoc::invalid_input))]
+    InvalidInput(Box<InputReport>),
+
+    #[error("no session token to talk to adventofcode.com")]
+    #[diagnostic(
+        code(aoc::missing_session),
+        help("set AOC_SESSION or put the session cookie in a .aoc-session file")
+    )]
+    MissingSession,
+
+    #[error("request for {url} failed: {reason}")]
+    #[diagnostic(code(aoc::http_error))]
+    Http { url: String, reason: String },
+
+    #[error("not submitting {answer}: {reason}")]
+    #[diagnostic(code(aoc::submission_refused))]
+    Refused { answer: String, reason: String },
+
+    #[error("{path} is not a valid ledger: {reason}")]
+    #[diagnostic(code(aoc::ledger_error))]
+    Ledger { path: String, reason: String },
+}
+
+impl From<InputReport> for AocError {
+    fn from(report: InputReport) -> Self {
+        AocError::InvalidInput(Box::new(report))
+    }
+}
+
+impl AocError {
+    // `remaining` has to be a slice of `src`, which is what nom hands back
+    pub fn parse(src: &str, remaining: &str, expected: impl Into<String>) -> Self {
+        let offset = src.offset(remaining).min(src.len());
+        let length = src[offset..].find('\n').unwrap_or(src.len() - offset);
+        AocError::Parse {
+            src: src.to_string(),
+            span: (offset, length).into(),
+            expected: expected.into(),
+        }
+    }
+
+    // Whatever a parser left of `src`, minus any whitespace before it. Points at
+    // the first thing that wasn't read, up to the end of its line.
+    pub fn trailing(src: &str, remaining: &str) -> Self {
+        let offset = src.offset(remaining.trim_start()).min(src.len());
+        let length = src[offset..].find('\n').unwrap_or(src.len() - offset);
+        let line_start = src[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+        AocError::TrailingInput {
+            src: src.to_string(),
+            span: (offset, length).into(),
+            line: src[..offset].matches('\n').count() + 1,
+            column: src[line_start..offset].chars().count() + 1,
+        }
+    }
+
+    pub fn from_nom(src: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                AocError::parse(src, error.input, error.code.description())
+            }
+            nom::Err::Incomplete(_) => AocError::parse(src, &src[src.len()..], "more input"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nom::{bytes::complete::tag, character::complete, sequence::preceded, IResult};
+
+    fn game_id(input: &str) -> IResult<&str, u32> {
+        preceded(tag("Game "), complete::u32)(input)
+    }
+
+    #[test]
+    fn trailing_input_has_a_line_and_column() {
+        let input = "Game 1\nGame 2\n  Game x\n";
+        let (rest, _) = game_id(input).unwrap();
+        let rest = &rest[rest.find("\n  Game x").unwrap()..];
+
+        match AocError::trailing(input, rest) {
+            AocError::TrailingInput {
+                span, line, column, ..
+            } => {
+                assert_eq!((line, column), (3, 3));
+                assert_eq!(&input[span.offset()..span.offset() + span.len()], "Game x");
+            }
+            other => panic!("expected trailing input, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_error_points_at_bad_line() {
+        let input = "Game 1\nGame x\nGame 3";
+        let line = input.lines().nth(1).unwrap();
+        let error = game_id(line).unwrap_err();
+
+        match AocError::from_nom(input, error) {
+            AocError::Parse { span, expected, .. } => {
+                assert_eq!(span.offset(), 12);
+                assert_eq!(span.len(), 1);
+                assert_eq!(expected, "Digit");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use std::ops::Index;
+
+use glam::IVec2;
+
+use crate::{report::line_spans, AocError, InputReport};
+
+// up, right, down, left
+pub const NEIGHBORS4: [IVec2; 4] = [IVec2::NEG_Y, IVec2::X, IVec2::Y, IVec2::NEG_X];
+
+// the four above plus the diagonals, clockwise from the top left
+pub const NEIGHBORS8: [IVec2; 8] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(1, 0),
+    IVec2::new(1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, 0),
+];
+
+// A rectangle of cells stored row by row. Positions are IVec2 with x the
+// column and y the row, so stepping off an edge is just a failed `get`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        Grid::parse_with(input, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "a {width}x{height} grid");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // One row per line, every line has to be as wide as the first
+    pub fn parse_with(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, AocError> {
+        let mut rows: Vec<_> = line_spans(input).map(|(_, span)| span).collect();
+        // a trailing newline leaves an empty last row behind
+        while rows.last().is_some_and(|span| span.is_empty()) {
+            rows.pop();
+        }
+
+        let width = rows
+            .first()
+            .map_or(0, |span| input[span.clone()].chars().count());
+        // every row that's off gets a label, not just the first one
+        let mut report = InputReport::new("grid rows are not all the same width", input);
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for (row, span) in rows.iter().enumerate() {
+            let line = &input[span.clone()];
+            let row_width = line.chars().count();
+            if row_width != width {
+                if report.labels.is_empty() {
+                    report =
+                        report.with_label(rows[0].clone(), format!("first row is {width} wide"));
+                }
+                report =
+                    report.with_label(span.clone(), format!("row {} is {row_width} wide", row + 1));
+                continue;
+            }
+            cells.extend(line.chars().map(&mut cell));
+        }
+
+        if !report.labels.is_empty() {
+            return Err(report.into());
+        }
+        Ok(Grid::new(width, rows.len(), cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: IVec2) -> bool {
+        (0..self.width as i32).contains(&position.x)
+            && (0..self.height as i32).contains(&position.y)
+    }
+
+    fn offset(&self, position: IVec2) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y as usize * self.width + position.x as usize)
+    }
+
+    pub fn get(&self, position: IVec2) -> Option<&T> {
+        self.offset(position).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, position: IVec2) -> Option<&mut T> {
+        self.offset(position).map(|offset| &mut self.cells[offset])
+    }
+
+    // Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.cells.iter().enumerate().map(|(offset, cell)| {
+            let position = IVec2::new((offset % self.width) as i32, (offset / self.width) as i32);
+            (position, cell)
+        })
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        position: IVec2,
+        directions: &'a [IVec2],
+    ) -> impl Iterator<Item = (IVec2, &'a T)> + 'a {
+        directions.iter().filter_map(move |&direction| {
+            let neighbor = position + direction;
+            self.get(neighbor).map(|cell| (neighbor, cell))
+        })
+    }
+
+    // The cells above, below and to either side that are on the grid
+    pub fn neighbors4(&self, position: IVec2) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.neighbors(position, &NEIGHBORS4)
+    }
+
+    // Like neighbors4 but with the diagonals too
+    pub fn neighbors8(&self, position: IVec2) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.neighbors(position, &NEIGHBORS8)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    // Columns aren't contiguous, so this walks down the rows instead of slicing
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: IVec2) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{position} is off the {}x{} grid", self.width, self.height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const INPUT: &str = "abc\ndef\nghi\n";
+
+    #[test]
+    fn parses_rows_and_columns() {
+        let grid = Grid::parse(INPUT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(3), None);
+        assert_eq!(
+            grid.column(2).unwrap().collect::<String>(),
+            "cfi".to_string()
+        );
+        assert!(grid.column(3).is_none());
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid[IVec2::new(2, 0)], 'c');
+    }
+
+    #[rstest]
+    #[case(IVec2::new(0, 0), Some('a'))]
+    #[case(IVec2::new(2, 2), Some('i'))]
+    #[case(IVec2::new(-1, 0), None)]
+    #[case(IVec2::new(0, 3), None)]
+    #[case(IVec2::new(3, 1), None)]
+    fn get_is_bounds_checked(#[case] position: IVec2, #[case] expected: Option<char>) {
+        let grid = Grid::parse(INPUT).unwrap();
+        assert_eq!(grid.get(position).copied(), expected);
+    }
+
+    #[rstest]
+    #[case(IVec2::new(1, 1), "bfhd", "abcfihgd")]
+    #[case(IVec2::new(0, 0), "bd", "bed")]
+    #[case(IVec2::new(2, 2), "fh", "efh")]
+    fn neighbors_stay_on_the_grid(
+        #[case] position: IVec2,
+        #[case] four: &str,
+        #[case] eight: &str,
+    ) {
+        let grid = Grid::parse(INPUT).unwrap();
+        let collect = |cells: &mut dyn Iterator<Item = (IVec2, &char)>| {
+            cells.map(|(_, &c)| c).collect::<String>()
+        };
+        assert_eq!(collect(&mut grid.neighbors4(position)), four);
+        assert_eq!(collect(&mut grid.neighbors8(position)), eight);
+    }
+
+    #[test]
+    fn ragged_rows_are_invalid_input() {
+        let Err(AocError::InvalidInput(report)) = Grid::parse("abc\nde\nfgh") else {
+            panic!("expected the short row to be reported");
+        };
+        assert_eq!(report.labels[1], (4..6, "row 2 is 2 wide".to_string()));
+    }
+
+    #[test]
+    fn every_ragged_row_is_labelled() {
+        let input = "467..114..
+...*......
+..35..633
+......#...
+617*.......
+";
+        let Err(AocError::InvalidInput(report)) = Grid::parse(input) else {
+            panic!("expected the ragged rows to be reported");
+        };
+        let labelled: Vec<(&str, &str)> = report
+            .labels
+            .iter()
+            .map(|(span, message)| (&input[span.clone()], message.as_str()))
+            .collect();
+        assert_eq!(
+            labelled,
+            vec![
+                ("467..114..", "first row is 10 wide"),
+                ("..35..633", "row 3 is 9 wide"),
+                ("617*.......", "row 5 is 11 wide"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_with_converts_cells() {
+        let grid = Grid::parse_with("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(grid.iter().map(|(_, &n)| n).sum::<u32>(), 10);
+        assert_eq!(grid.map(|n| n * 2)[IVec2::new(1, 1)], 8);
+    }
+}
@@ -0,0 +1,114 @@
+// Heap profiling for the binaries with the `dhat-heap` feature. Hold on to the
+// guard from `profile` for the whole run, dropping it prints a summary and
+// writes dhat-heap.json for https://nnethercote.github.io/dh_view/dh_view.html
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+// dhat panics when asked for stats without a profiler
+#[cfg(feature = "dhat-heap")]
+static RUNNING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeapUsage {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    pub max_bytes: usize,
+    pub max_blocks: usize,
+}
+
+impl HeapUsage {
+    // What was allocated between `earlier` and now, the peak can't be split
+    // up so it stays the high-water mark of the whole run
+    pub fn since(&self, earlier: &HeapUsage) -> HeapUsage {
+        HeapUsage {
+            total_bytes: self.total_bytes - earlier.total_bytes,
+            total_blocks: self.total_blocks - earlier.total_blocks,
+            ..*self
+        }
+    }
+}
+
+impl std::fmt::Display for HeapUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} bytes in {} allocations, peak {} bytes in {} blocks",
+            self.total_bytes, self.total_blocks, self.max_bytes, self.max_blocks
+        )
+    }
+}
+
+// None unless built with `dhat-heap` and a profile is running
+pub fn usage() -> Option<HeapUsage> {
+    #[cfg(feature = "dhat-heap")]
+    {
+        if !RUNNING.load(std::sync::atomic::Ordering::SeqCst) {
+            return None;
+        }
+        let stats = dhat::HeapStats::get();
+        Some(HeapUsage {
+            total_bytes: stats.total_bytes,
+            total_blocks: stats.total_blocks,
+            max_bytes: stats.max_bytes,
+            max_blocks: stats.max_blocks,
+        })
+    }
+    #[cfg(not(feature = "dhat-heap"))]
+    None
+}
+
+pub struct HeapProfile {
+    name: String,
+    #[cfg(feature = "dhat-heap")]
+    _profiler: dhat::Profiler,
+}
+
+pub fn profile(name: &str) -> HeapProfile {
+    #[cfg(feature = "dhat-heap")]
+    {
+        HeapProfile::start(name, dhat::Profiler::new_heap())
+    }
+    #[cfg(not(feature = "dhat-heap"))]
+    HeapProfile {
+        name: name.to_string(),
+    }
+}
+
+#[cfg(feature = "dhat-heap")]
+impl HeapProfile {
+    fn start(name: &str, profiler: dhat::Profiler) -> Self {
+        RUNNING.store(true, std::sync::atomic::Ordering::SeqCst);
+        HeapProfile {
+            name: name.to_string(),
+            _profiler: profiler,
+        }
+    }
+}
+
+impl Drop for HeapProfile {
+    fn drop(&mut self) {
+        if let Some(usage) = usage() {
+            eprintln!("{}: {usage}", self.name);
+        }
+        #[cfg(feature = "dhat-heap")]
+        RUNNING.store(false, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[cfg(all(test, feature = "dhat-heap"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_allocations() {
+        let _profile = HeapProfile::start("test", dhat::Profiler::builder().testing().build());
+        let before = usage().unwrap();
+        let numbers: Vec<u64> = (0..100).collect();
+        let after = usage().unwrap();
+
+        assert_eq!(numbers.len(), 100);
+        assert_eq!(after.since(&before).total_blocks, 1);
+        assert_eq!(after.since(&before).total_bytes, 800);
+    }
+}
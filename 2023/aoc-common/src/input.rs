@@ -0,0 +1,186 @@
+use std::{
+    env, fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::{telemetry::is_verbosity_flag, AocError};
+
+pub const YEAR: u16 = 2023;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    // inputs/<year>/<day>.txt, looked up from the current directory upwards
+    Conventional { year: u16, day: String },
+}
+
+impl InputSource {
+    // `-` reads stdin, anything else is a path, nothing falls back to the usual spot
+    pub fn from_arg(arg: Option<&str>, day: &str) -> Self {
+        match arg {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => InputSource::Conventional {
+                year: YEAR,
+                day: day.to_string(),
+            },
+        }
+    }
+
+    pub fn load(&self) -> Result<String, AocError> {
+        match self {
+            InputSource::File(path) => read_file(path),
+            InputSource::Stdin => Ok(io::read_to_string(io::stdin())?),
+            InputSource::Conventional { year, day } => {
+                let cwd = env::current_dir()?;
+                read_file(&conventional_path(&cwd, *year, day))
+            }
+        }
+    }
+}
+
+// day-03, the crate and input file name for a day
+pub fn day_name(day: u8) -> String {
+    format!("day-{day:02}")
+}
+
+// The first `inputs` directory found from `start` upwards
+pub fn inputs_dir(start: &Path) -> PathBuf {
+    start
+        .ancestors()
+        .map(|dir| dir.join("inputs"))
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| start.join("inputs"))
+}
+
+// The first ancestor of `start` holding inputs/<year>/<day>.txt, or the path
+// relative to `start` when there isn't one so the error names what was missing
+pub fn conventional_path(start: &Path, year: u16, day: &str) -> PathBuf {
+    let relative = Path::new("inputs")
+        .join(year.to_string())
+        .join(format!("{day}.txt"));
+    start
+        .ancestors()
+        .map(|dir| dir.join(&relative))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| start.join(relative))
+}
+
+// Reads a file, an error names the path it couldn't read
+pub fn read_file(path: &Path) -> Result<String, AocError> {
+    fs::read_to_string(path).map_err(|error| {
+        let message = match error.kind() {
+            ErrorKind::NotFound => format!("no puzzle input at {}", path.display()),
+            _ => format!("reading {}: {error}", path.display()),
+        };
+        AocError::IoError(io::Error::new(error.kind(), message))
+    })
+}
+
+// The first argument that isn't a -v or a --flag
+fn input_arg<I: IntoIterator<Item = String>>(args: I) -> Option<String> {
+    args.into_iter()
+        .find(|arg| !is_verbosity_flag(arg) && !arg.starts_with("--"))
+}
+
+// Input for a day binary, taken from its command line
+pub fn read_input(day: &str) -> Result<String, AocError> {
+    let arg = input_arg(env::args().skip(1));
+    InputSource::from_arg(arg.as_deref(), day).load()
+}
+
+// Whether a day binary was passed `--<name>`
+pub fn has_flag(name: &str) -> bool {
+    env::args()
+        .skip(1)
+        .any(|arg| arg.strip_prefix("--") == Some(name))
+}
+
+fn value_of<I: IntoIterator<Item = String>>(args: I, name: &str) -> Option<String> {
+    args.into_iter().find_map(|arg| {
+        let value = arg
+            .strip_prefix("--")?
+            .strip_prefix(name)?
+            .strip_prefix('=')?;
+        Some(value.to_string())
+    })
+}
+
+// The value of a `--<name>=<value>` flag passed to a day binary. Only the
+// `=` form, so the value can't be mistaken for the input path.
+pub fn flag_value(name: &str) -> Option<String> {
+    value_of(env::args().skip(1), name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_inputs_in_a_parent_directory() {
+        let root = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        let nested = root.join("2023").join("day-01");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir_all(root.join("inputs/2023")).unwrap();
+        fs::write(root.join("inputs/2023/day-01.txt"), "1abc2").unwrap();
+
+        let path = conventional_path(&nested, 2023, "day-01");
+        assert_eq!(path, root.join("inputs/2023/day-01.txt"));
+        assert_eq!(InputSource::File(path).load().unwrap(), "1abc2");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn missing_input_is_an_io_error() {
+        let path = env::temp_dir().join("aoc-input-that-does-not-exist.txt");
+        match InputSource::File(path.clone()).load() {
+            Err(AocError::IoError(error)) => {
+                assert_eq!(error.kind(), ErrorKind::NotFound);
+                assert!(error.to_string().contains(&path.display().to_string()));
+            }
+            other => panic!("expected an io error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn dash_means_stdin() {
+        assert_eq!(
+            InputSource::from_arg(Some("-"), "day-01"),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            InputSource::from_arg(None, "day-01"),
+            InputSource::Conventional {
+                year: 2023,
+                day: "day-01".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn flags_are_not_the_input() {
+        let args = |args: &[&str]| input_arg(args.iter().map(|arg| arg.to_string()));
+        assert_eq!(
+            args(&["-v", "--lenient", "in.txt"]),
+            Some("in.txt".to_string())
+        );
+        assert_eq!(args(&["--lenient", "-"]), Some("-".to_string()));
+        assert_eq!(args(&["--verbose"]), None);
+    }
+
+    #[test]
+    fn flag_values_come_after_an_equals_sign() {
+        let args = || {
+            ["in.txt", "--bag-file=bag.txt", "--bag=12 red, 1 blue"]
+                .iter()
+                .map(|arg| arg.to_string())
+        };
+        assert_eq!(value_of(args(), "bag"), Some("12 red, 1 blue".to_string()));
+        assert_eq!(value_of(args(), "bag-file"), Some("bag.txt".to_string()));
+        assert_eq!(value_of(args(), "lenient"), None);
+        assert_eq!(input_arg(args()), Some("in.txt".to_string()));
+    }
+}
@@ -0,0 +1,13 @@
+pub mod answer;
+pub mod custom_error;
+pub mod grid;
+pub mod heap;
+pub mod input;
+pub mod parse;
+pub mod report;
+pub mod spans;
+pub mod telemetry;
+
+pub use answer::Answer;
+pub use custom_error::AocError;
+pub use report::InputReport;
@@ -0,0 +1,215 @@
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::{tag, take_till1},
+    character::complete::{char, digit1, line_ending, space0, space1},
+    combinator::{map_res, opt, recognize},
+    error::Error,
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    IResult, Parser,
+};
+
+use crate::{grid::Grid, AocError};
+
+// Run `parser` over the whole input. Trailing whitespace is fine, anything
+// else left over is an error: a parser that stops early on a line it didn't
+// expect would otherwise give an answer for half the input.
+pub fn parse_all<'a, O, P>(input: &'a str, mut parser: P) -> Result<O, AocError>
+where
+    P: Parser<&'a str, O, Error<&'a str>>,
+{
+    let (rest, output) = parser
+        .parse(input)
+        .map_err(|error| AocError::from_nom(input, error))?;
+    if !rest.trim().is_empty() {
+        return Err(AocError::trailing(input, rest));
+    }
+    Ok(output)
+}
+
+// 42, into whatever unsigned (or signed) type N is. Too big for N is a MapRes error.
+pub fn unsigned<N: FromStr>(input: &str) -> IResult<&str, N> {
+    map_res(digit1, str::parse)(input)
+}
+
+// -42 or 42
+pub fn signed<N: FromStr>(input: &str) -> IResult<&str, N> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+// ` 41 48  83 86` - numbers on one line, any number of spaces or tabs
+// before and between them
+pub fn unsigned_list<N: FromStr>(input: &str) -> IResult<&str, Vec<N>> {
+    preceded(space0, separated_list1(space1, unsigned))(input)
+}
+
+pub fn signed_list<N: FromStr>(input: &str) -> IResult<&str, Vec<N>> {
+    preceded(space0, separated_list1(space1, signed))(input)
+}
+
+// `Card   1` - a label and the number that goes with it
+pub fn labelled<'a, N: FromStr>(label: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, N> {
+    preceded(pair(tag(label), space1), unsigned)
+}
+
+// `key: value`, with or without spaces around the colon. The value can start
+// on the next line if it parses a line ending first.
+pub fn key_value<'a, K, V, P, Q>(
+    key: P,
+    value: Q,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+where
+    P: Parser<&'a str, K, Error<&'a str>>,
+    Q: Parser<&'a str, V, Error<&'a str>>,
+{
+    separated_pair(key, tuple((space0, char(':'), space0)), value)
+}
+
+// One or more lines of `line`
+pub fn lines<'a, O, P>(line: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    P: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(line_ending, line)
+}
+
+// The gap between two blocks: the end of a line and at least one empty line
+pub fn blank_lines(input: &str) -> IResult<&str, &str> {
+    recognize(pair(line_ending, many1(terminated(space0, line_ending))))(input)
+}
+
+// Sections separated by blank lines, like the maps in an almanac
+pub fn blocks<'a, O, P>(block: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    P: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(blank_lines, block)
+}
+
+// Lines of characters up to the end of the input or a blank line, made into
+// a grid by Grid::parse. Rows that aren't all as wide as the first don't stop
+// the parser, the grid comes back as the same labelled report Grid::parse
+// gives: `parse_all(input, char_grid)??`
+pub fn char_grid(input: &str) -> IResult<&str, Result<Grid<char>, AocError>> {
+    let (rest, block) = recognize(lines(take_till1(|c| c == '\n' || c == '\r')))(input)?;
+    Ok((rest, Grid::parse(block)))
+}
+
+// `| ` style separators with whatever spacing around them
+pub fn padded<'a>(separator: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    delimited(space0, tag(separator), space0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use glam::IVec2;
+    use nom::character::complete::alpha1;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1 2\n3 4\n", vec![vec![1, 2], vec![3, 4]])]
+    #[case("1 2\n3 4\n\n  \n", vec![vec![1, 2], vec![3, 4]])]
+    fn parse_all_allows_trailing_whitespace(#[case] input: &str, #[case] expected: Vec<Vec<u32>>) {
+        assert_eq!(
+            parse_all(input, lines(unsigned_list::<u32>)).unwrap(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("1 2\n3 x\n5 6", (2, 3))]
+    #[case("1 2\n3 4\n\n5 6", (4, 1))]
+    #[case("1 2 |", (1, 5))]
+    fn parse_all_reports_where_it_stopped(#[case] input: &str, #[case] expected: (usize, usize)) {
+        match parse_all(input, lines(unsigned_list::<u32>)) {
+            Err(AocError::TrailingInput { line, column, .. }) => {
+                assert_eq!((line, column), expected)
+            }
+            other => panic!("expected trailing input, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case("41 48 83", vec![41, 48, 83], "")]
+    #[case("  1 21  53\t59 | 69", vec![1, 21, 53, 59], " | 69")]
+    #[case("7\n8", vec![7], "\n8")]
+    fn unsigned_lists(#[case] input: &str, #[case] expected: Vec<u32>, #[case] rest: &str) {
+        assert_eq!(unsigned_list::<u32>(input), Ok((rest, expected)));
+    }
+
+    #[test]
+    fn signed_lists() {
+        assert_eq!(
+            signed_list::<i64>("0 -3  6 -9"),
+            Ok(("", vec![0, -3, 6, -9]))
+        );
+        assert!(unsigned_list::<u32>("-3").is_err());
+    }
+
+    #[test]
+    fn numbers_too_big_for_the_type_fail() {
+        assert!(unsigned::<u8>("256").is_err());
+        assert_eq!(unsigned::<u64>("4294967296"), Ok(("", 4294967296)));
+    }
+
+    #[rstest]
+    #[case("Card   1: 41 48", 1, vec![41, 48])]
+    #[case("Game 12:3", 12, vec![3])]
+    #[case("Card 7 :  5", 7, vec![5])]
+    fn key_values(#[case] input: &str, #[case] id: u32, #[case] values: Vec<u32>) {
+        let (_, parsed) =
+            key_value(labelled::<u32>("Card").or(labelled("Game")), unsigned_list)(input).unwrap();
+        assert_eq!(parsed, (id, values));
+    }
+
+    #[test]
+    fn padded_separators() {
+        let mut card = separated_pair(unsigned_list::<u32>, padded("|"), unsigned_list::<u32>);
+        assert_eq!(
+            card("41 48 |  83 86"),
+            Ok(("", (vec![41, 48], vec![83, 86])))
+        );
+        assert_eq!(card("1|2"), Ok(("", (vec![1], vec![2]))));
+    }
+
+    #[test]
+    fn blocks_split_on_blank_lines() {
+        let input = "a:\n1 2\n\nb:\n3\n \n\nc:\n4 5";
+        let block = key_value(alpha1, preceded(line_ending, lines(unsigned_list::<u32>)));
+        let (rest, parsed) = blocks(block)(input).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(
+            parsed,
+            vec![
+                ("a", vec![vec![1, 2]]),
+                ("b", vec![vec![3]]),
+                ("c", vec![vec![4, 5]])
+            ]
+        );
+    }
+
+    #[test]
+    fn grids_stop_at_a_blank_line() {
+        let (rest, grid) = char_grid("#.#\n.#.\n\n...").unwrap();
+        let grid = grid.unwrap();
+        assert_eq!(rest, "\n\n...");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[IVec2::new(1, 1)], '#');
+    }
+
+    #[test]
+    fn ragged_grids_are_labelled_on_the_short_row() {
+        let input = "#.#\n.#\n###";
+        let Err(AocError::InvalidInput(report)) = parse_all(input, char_grid).unwrap() else {
+            panic!("expected the short row to be reported");
+        };
+        let (span, label) = &report.labels[1];
+        assert_eq!(
+            (&input[span.clone()], label.as_str()),
+            (".#", "row 2 is 2 wide")
+        );
+    }
+}
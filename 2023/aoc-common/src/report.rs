@@ -0,0 +1,111 @@
+use std::{fmt, ops::Range};
+
+use ariadne::{Config, Label, Report, ReportKind, Source};
+
+// An input problem that can point at more than one place in the puzzle input,
+// rendered with ariadne so every offending region gets its own label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputReport {
+    pub message: String,
+    pub src: String,
+    // byte ranges into `src`
+    pub labels: Vec<(Range<usize>, String)>,
+    pub note: Option<String>,
+}
+
+impl InputReport {
+    pub fn new(message: impl Into<String>, src: &str) -> Self {
+        InputReport {
+            message: message.into(),
+            src: src.to_string(),
+            labels: Vec::new(),
+            note: None,
+        }
+    }
+
+    pub fn with_label(mut self, span: Range<usize>, message: impl Into<String>) -> Self {
+        self.labels.push((span, message.into()));
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    // ariadne counts in chars, the labels are stored as byte offsets
+    fn char_span(&self, span: &Range<usize>) -> Range<usize> {
+        let start = self.src[..span.start].chars().count();
+        start..start + self.src[span.clone()].chars().count()
+    }
+
+    pub fn render(&self, color: bool) -> String {
+        let offset = self
+            .labels
+            .first()
+            .map(|(span, _)| self.char_span(span).start)
+            .unwrap_or(0);
+
+        let mut report = Report::build(ReportKind::Error, (), offset)
+            .with_config(Config::default().with_color(color))
+            .with_message(&self.message)
+            .with_labels(
+                self.labels
+                    .iter()
+                    .map(|(span, message)| Label::new(self.char_span(span)).with_message(message)),
+            );
+        if let Some(note) = &self.note {
+            report = report.with_note(note);
+        }
+
+        let mut out = Vec::new();
+        report
+            .finish()
+            .write(Source::from(&self.src), &mut out)
+            .expect("writing to a Vec can't fail");
+        String::from_utf8_lossy(&out).into_owned()
+    }
+}
+
+impl fmt::Display for InputReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(false))
+    }
+}
+
+// Byte range of every line in the input, without the line ending
+pub fn line_spans(input: &str) -> impl Iterator<Item = (usize, Range<usize>)> + '_ {
+    input
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            let content = line.trim_end_matches(['\n', '\r']);
+            Some(start..start + content.len())
+        })
+        .enumerate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_every_region() {
+        let src = "abc\nabcd\nab\n";
+        let report = InputReport::new("rows differ", src)
+            .with_label(4..8, "row 2 is 4 wide")
+            .with_label(9..11, "row 3 is 2 wide");
+
+        let rendered = report.render(false);
+        assert!(rendered.contains("rows differ"));
+        assert!(rendered.contains("row 2 is 4 wide"));
+        assert!(rendered.contains("row 3 is 2 wide"));
+    }
+
+    #[test]
+    fn line_spans_skip_line_endings() {
+        let spans: Vec<_> = line_spans("ab\r\ncd\n\nef").collect();
+        assert_eq!(spans, vec![(0, 0..2), (1, 4..6), (2, 7..7), (3, 8..10)]);
+    }
+}
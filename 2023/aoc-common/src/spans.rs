@@ -0,0 +1,250 @@
+use std::{ops::Range, str::FromStr};
+
+use glam::IVec2;
+
+use crate::{grid::Grid, report::line_spans, AocError, InputReport};
+
+// Where a span sits in the list it was found in. Spans are found row by row,
+// left to right, so the same grid always hands out the same ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpanId(pub usize);
+
+// A token that runs across neighbouring cells of one row, like 467 in
+// `467..114..`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span<V> {
+    pub id: SpanId,
+    pub value: V,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
+
+impl<V> Span<V> {
+    pub fn start(&self) -> IVec2 {
+        IVec2::new(self.columns.start as i32, self.row as i32)
+    }
+
+    // every cell the span covers
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.columns
+            .clone()
+            .map(|column| IVec2::new(column as i32, self.row as i32))
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(V) -> U) -> Span<U> {
+        Span {
+            id: self.id,
+            value: f(self.value),
+            row: self.row,
+            columns: self.columns,
+        }
+    }
+}
+
+impl Grid<char> {
+    // Runs of neighbouring cells in a row that all match `belongs`, with the
+    // text they spell out
+    pub fn runs(&self, belongs: impl Fn(char) -> bool) -> Vec<Span<String>> {
+        let mut spans = Vec::new();
+        for (row, cells) in self.rows().enumerate() {
+            let mut column = 0;
+            while column < cells.len() {
+                if !belongs(cells[column]) {
+                    column += 1;
+                    continue;
+                }
+                let start = column;
+                while column < cells.len() && belongs(cells[column]) {
+                    column += 1;
+                }
+                spans.push(Span {
+                    id: SpanId(spans.len()),
+                    value: cells[start..column].iter().collect(),
+                    row,
+                    columns: start..column,
+                });
+            }
+        }
+        spans
+    }
+
+    pub fn runs_of(&self, c: char) -> Vec<Span<String>> {
+        self.runs(|cell| cell == c)
+    }
+
+    pub fn words(&self) -> Vec<Span<String>> {
+        self.runs(char::is_alphabetic)
+    }
+
+    // Every run of digits, parsed. A run too long for N is invalid input,
+    // labelled in `input`, the text the grid was parsed from.
+    pub fn numbers<N: FromStr>(&self, input: &str) -> Result<Vec<Span<N>>, AocError> {
+        self.runs(|c| c.is_ascii_digit())
+            .into_iter()
+            .map(|span| match span.value.parse::<N>() {
+                Ok(value) => Ok(span.map(|_| value)),
+                Err(_) => {
+                    let (_, line) = line_spans(input).nth(span.row).unwrap_or((0, 0..0));
+                    let start = line.start
+                        + input[line]
+                            .chars()
+                            .take(span.columns.start)
+                            .map(char::len_utf8)
+                            .sum::<usize>();
+                    Err(
+                        InputReport::new(format!("{} doesn't fit in a number", span.value), input)
+                            .with_label(
+                                start..start + span.value.len(),
+                                format!("row {}", span.row + 1),
+                            )
+                            .into(),
+                    )
+                }
+            })
+            .collect()
+    }
+}
+
+// Spans together with a lookup from every cell to the span covering it, so
+// finding what's next to a cell doesn't mean scanning every span
+#[derive(Debug, Clone)]
+pub struct SpanIndex<V> {
+    spans: Vec<Span<V>>,
+    cells: Grid<Option<SpanId>>,
+}
+
+impl<V> SpanIndex<V> {
+    // `spans` have to come from `grid`, in the order they were found in. Some
+    // can be left out, the rest keep their ids.
+    pub fn new<T>(grid: &Grid<T>, spans: Vec<Span<V>>) -> Self {
+        let mut cells = Grid::new(
+            grid.width(),
+            grid.height(),
+            vec![None; grid.width() * grid.height()],
+        );
+        for span in &spans {
+            for position in span.positions() {
+                if let Some(cell) = cells.get_mut(position) {
+                    *cell = Some(span.id);
+                }
+            }
+        }
+        SpanIndex { spans, cells }
+    }
+
+    pub fn get(&self, id: SpanId) -> Option<&Span<V>> {
+        self.spans
+            .binary_search_by_key(&id, |span| span.id)
+            .ok()
+            .map(|found| &self.spans[found])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Span<V>> + '_ {
+        self.spans.iter()
+    }
+
+    // The span covering a cell, if any
+    pub fn at(&self, position: IVec2) -> Option<&Span<V>> {
+        self.cells
+            .get(position)
+            .copied()
+            .flatten()
+            .and_then(|id| self.get(id))
+    }
+
+    // Spans covering the cell or any of its eight neighbours, each one once
+    pub fn touching(&self, position: IVec2) -> impl Iterator<Item = &Span<V>> + '_ {
+        let mut ids: Vec<SpanId> = self
+            .cells
+            .get(position)
+            .into_iter()
+            .chain(self.cells.neighbors8(position).map(|(_, id)| id))
+            .filter_map(|id| *id)
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids.into_iter().filter_map(|id| self.get(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const SCHEMATIC: &str = "467..114..
+...*......
+..35..633.";
+
+    #[test]
+    fn numbers_carry_their_cells() {
+        let grid = Grid::parse(SCHEMATIC).unwrap();
+        let numbers = grid.numbers::<u32>(SCHEMATIC).unwrap();
+        assert_eq!(
+            numbers
+                .iter()
+                .map(|span| (span.id.0, span.value, span.row, span.columns.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, 467, 0, 0..3),
+                (1, 114, 0, 5..8),
+                (2, 35, 2, 2..4),
+                (3, 633, 2, 6..9)
+            ]
+        );
+        assert_eq!(numbers[2].start(), IVec2::new(2, 2));
+    }
+
+    #[test]
+    fn words_and_runs() {
+        let grid = Grid::parse("ab..cd\n##.ef#").unwrap();
+        let values =
+            |spans: Vec<Span<String>>| spans.into_iter().map(|span| span.value).collect::<Vec<_>>();
+        assert_eq!(values(grid.words()), vec!["ab", "cd", "ef"]);
+        assert_eq!(values(grid.runs_of('#')), vec!["##", "#"]);
+        assert_eq!(values(grid.runs_of('.')), vec!["..", "."]);
+    }
+
+    #[test]
+    fn too_big_a_number_is_labelled_in_the_input() {
+        let input = "..1..........\r\n.............\r\n..99999999999";
+        let grid = Grid::parse(input).unwrap();
+        let Err(AocError::InvalidInput(report)) = grid.numbers::<u32>(input) else {
+            panic!("expected the long number to be reported");
+        };
+        assert_eq!(report.src, input);
+        let (span, label) = &report.labels[0];
+        assert_eq!(
+            (&input[span.clone()], label.as_str()),
+            ("99999999999", "row 3")
+        );
+    }
+
+    #[rstest]
+    // the gear between 467 and 35
+    #[case(IVec2::new(3, 1), vec![467, 35])]
+    #[case(IVec2::new(0, 0), vec![467])]
+    #[case(IVec2::new(9, 2), vec![633])]
+    #[case(IVec2::new(9, 0), vec![])]
+    #[case(IVec2::new(-1, 0), vec![467])]
+    fn touching_spans(#[case] position: IVec2, #[case] expected: Vec<u32>) {
+        let grid = Grid::parse(SCHEMATIC).unwrap();
+        let index = SpanIndex::new(&grid, grid.numbers::<u32>(SCHEMATIC).unwrap());
+        assert_eq!(
+            index
+                .touching(position)
+                .map(|span| span.value)
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn cells_map_back_to_their_span() {
+        let grid = Grid::parse(SCHEMATIC).unwrap();
+        let index = SpanIndex::new(&grid, grid.numbers::<u32>(SCHEMATIC).unwrap());
+        assert_eq!(index.at(IVec2::new(7, 2)).map(|span| span.value), Some(633));
+        assert!(index.at(IVec2::new(3, 1)).is_none());
+    }
+}
@@ -0,0 +1,84 @@
+use std::{env, io};
+
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+// -v, -vv, ... and --verbose, which the binaries take alongside the input path
+pub fn is_verbosity_flag(arg: &str) -> bool {
+    arg == "--verbose"
+        || (arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v'))
+}
+
+pub fn verbosity<I: IntoIterator<Item = S>, S: AsRef<str>>(args: I) -> u8 {
+    args.into_iter()
+        .filter(|arg| is_verbosity_flag(arg.as_ref()))
+        .map(|arg| match arg.as_ref() {
+            "--verbose" => 1,
+            flag => flag.len() as u8 - 1,
+        })
+        .fold(0, u8::saturating_add)
+}
+
+// RUST_LOG wins when it's set, otherwise only warnings unless asked for more:
+// -v shows debug events and -vv everything down to trace
+pub fn filter(verbosity: u8) -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new(match verbosity {
+            0 => "warn",
+            1 => "debug",
+            _ => "trace",
+        })
+    })
+}
+
+// Subscriber setup for the day binaries, verbosity comes from their arguments
+pub fn init() {
+    init_with_verbosity(verbosity(env::args().skip(1)));
+}
+
+// Events go to stderr so the answer on stdout stays clean. With the `tracy`
+// feature every span also goes to Tracy, whatever the log filter says.
+pub fn init_with_verbosity(verbosity: u8) {
+    let registry = tracing_subscriber::registry().with(
+        fmt::layer()
+            .with_writer(io::stderr)
+            .without_time()
+            .with_filter(filter(verbosity)),
+    );
+    #[cfg(feature = "tracy")]
+    let registry = registry.with(tracing_tracy::TracyLayer::new());
+    registry.init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&[], 0)]
+    #[case(&["input.txt"], 0)]
+    #[case(&["-v", "input.txt"], 1)]
+    #[case(&["input.txt", "-vv"], 2)]
+    #[case(&["-v", "--verbose", "-"], 2)]
+    fn counts_verbosity_flags(#[case] args: &[&str], #[case] expected: u8) {
+        assert_eq!(verbosity(args), expected);
+    }
+
+    #[test]
+    fn dash_is_not_a_flag() {
+        assert!(!is_verbosity_flag("-"));
+        assert!(!is_verbosity_flag("-x"));
+        assert!(is_verbosity_flag("-vvv"));
+    }
+
+    // spans have to be fine with nothing listening on the other end
+    #[cfg(feature = "tracy")]
+    #[test]
+    fn tracy_layer_without_a_profiler() {
+        init_with_verbosity(0);
+        let span = tracing::info_span!("solve", day = 1);
+        let _entered = span.enter();
+        tracing::info!("still running");
+    }
+}
@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use aoc::registry::{self, Solution};
+use aoc_common::input::{conventional_path, YEAR};
+use divan::Bencher;
+
+fn main() {
+    // Run registered benchmarks.
+    divan::main();
+}
+
+// Every registered day and part against its real input, one row each
+#[divan::bench(args = registry::solutions())]
+fn process(bencher: Bencher, solution: &Solution) {
+    let path = conventional_path(
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        YEAR,
+        &solution.crate_name(),
+    );
+    let Ok(input) = std::fs::read_to_string(&path) else {
+        eprintln!("skipping {solution}, no input at {}", path.display());
+        return;
+    };
+
+    bencher.bench(|| (solution.solve)(divan::black_box(&input)).unwrap());
+}
@@ -0,0 +1,258 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{
+    input::{day_name, inputs_dir, read_file},
+    AocError,
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/nickw8/AoC input fetcher";
+
+// Somewhere puzzle inputs can come from
+pub trait InputBackend {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, AocError>;
+}
+
+// Where to get inputs from, read from the environment:
+// AOC_SESSION (or a .aoc-session file), AOC_BASE_URL and AOC_OFFLINE_DIR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchConfig {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub offline_dir: Option<PathBuf>,
+}
+
+impl FetchConfig {
+    pub fn from_env(start: &Path) -> Self {
+        let session = env::var("AOC_SESSION").ok().or_else(|| {
+            start
+                .ancestors()
+                .map(|dir| dir.join(".aoc-session"))
+                .find_map(|path| fs::read_to_string(path).ok())
+        });
+        FetchConfig {
+            base_url: env::var("AOC_BASE_URL").unwrap_or_else(|_| BASE_URL.to_string()),
+            session: session
+                .map(|session| session.trim().to_string())
+                .filter(|session| !session.is_empty()),
+            offline_dir: env::var_os("AOC_OFFLINE_DIR").map(PathBuf::from),
+        }
+    }
+
+    pub fn backend(&self) -> Result<Box<dyn InputBackend>, AocError> {
+        match &self.offline_dir {
+            Some(root) => Ok(Box::new(DirectoryBackend::new(root))),
+            None => {
+                let session = self.session.clone().ok_or(AocError::MissingSession)?;
+                Ok(Box::new(HttpBackend::new(&self.base_url, session)))
+            }
+        }
+    }
+}
+
+pub struct HttpBackend {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl HttpBackend {
+    pub fn new(base_url: &str, session: String) -> Self {
+        HttpBackend {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub(crate) fn agent(&self) -> &ureq::Agent {
+        &self.agent
+    }
+
+    pub(crate) fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+impl InputBackend for HttpBackend {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, AocError> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let http_error = |reason: String| AocError::Http {
+            url: url.clone(),
+            reason,
+        };
+
+        match self.agent.get(&url).set("Cookie", &self.cookie()).call() {
+            Ok(response) => response
+                .into_string()
+                .map_err(|error| http_error(error.to_string())),
+            Err(ureq::Error::Status(404, _)) => {
+                Err(http_error("the puzzle isn't unlocked yet".to_string()))
+            }
+            Err(ureq::Error::Status(400 | 500, _)) => Err(http_error(
+                "the session token was rejected, it has probably expired".to_string(),
+            )),
+            Err(error) => Err(http_error(error.to_string())),
+        }
+    }
+}
+
+// Inputs copied somewhere by hand, laid out as <root>/<year>/<day>.txt
+pub struct DirectoryBackend {
+    root: PathBuf,
+}
+
+impl DirectoryBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        DirectoryBackend { root: root.into() }
+    }
+}
+
+impl InputBackend for DirectoryBackend {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, AocError> {
+        let path = self
+            .root
+            .join(year.to_string())
+            .join(format!("{}.txt", day_name(day)));
+        read_file(&path)
+    }
+}
+
+// Inputs are only fetched once, after that they're read from `dir`,
+// which is the same inputs/<year>/<day>.txt the day binaries load from
+pub struct InputCache<B> {
+    dir: PathBuf,
+    backend: B,
+}
+
+impl<B: InputBackend> InputCache<B> {
+    pub fn new(dir: impl Into<PathBuf>, backend: B) -> Self {
+        InputCache {
+            dir: dir.into(),
+            backend,
+        }
+    }
+
+    // The repo's inputs directory, found from the current directory upwards
+    pub fn in_repo(backend: B) -> Result<Self, AocError> {
+        Ok(InputCache::new(inputs_dir(&env::current_dir()?), backend))
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("{}.txt", day_name(day)))
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Result<String, AocError> {
+        let path = self.path(year, day);
+        if let Ok(input) = fs::read_to_string(&path) {
+            return Ok(input);
+        }
+
+        let input = self.backend.fetch(year, day)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+}
+
+impl<B: InputBackend + ?Sized> InputBackend for Box<B> {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, AocError> {
+        (**self).fetch(year, day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_server::StubServer;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn http_backend_sends_the_session() -> miette::Result<()> {
+        let server = StubServer::start(|_| (200, "1abc2\n".to_string()));
+        let backend = HttpBackend::new(&server.base_url, "abc123".to_string());
+
+        assert_eq!(backend.fetch(2023, 1)?, "1abc2\n");
+        let requests = server.requests();
+        assert_eq!(requests[0].url, "/2023/day/1/input");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+        Ok(())
+    }
+
+    #[test]
+    fn locked_puzzle_is_an_error() {
+        let server = StubServer::start(|_| (404, "Not Found".to_string()));
+        let backend = HttpBackend::new(&server.base_url, "abc123".to_string());
+
+        let Err(AocError::Http { reason, .. }) = backend.fetch(2023, 25) else {
+            panic!("expected a 404 to be an error");
+        };
+        assert!(reason.contains("unlocked"));
+    }
+
+    #[test]
+    fn cache_only_fetches_once() -> miette::Result<()> {
+        let server = StubServer::start(|_| (200, "seeds: 1 2\n".to_string()));
+        let dir = scratch_dir("cache");
+        let cache = InputCache::new(
+            &dir,
+            HttpBackend::new(&server.base_url, "abc123".to_string()),
+        );
+
+        assert_eq!(cache.get(2023, 5)?, "seeds: 1 2\n");
+        assert_eq!(cache.get(2023, 5)?, "seeds: 1 2\n");
+        assert_eq!(server.requests().len(), 1);
+        assert!(dir.join("2023/day-05.txt").is_file());
+
+        fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn directory_backend_reads_offline_copies() -> miette::Result<()> {
+        let offline = scratch_dir("offline");
+        fs::create_dir_all(offline.join("2023")).unwrap();
+        fs::write(offline.join("2023/day-02.txt"), "Game 1: 1 red").unwrap();
+        let cache_dir = scratch_dir("offline-cache");
+
+        let cache = InputCache::new(&cache_dir, DirectoryBackend::new(&offline));
+        assert_eq!(cache.get(2023, 2)?, "Game 1: 1 red");
+        let Err(AocError::IoError(error)) = cache.get(2023, 3) else {
+            panic!("expected a missing offline copy to be an io error");
+        };
+        let missing = offline.join("2023/day-03.txt");
+        assert!(error.to_string().contains(&missing.display().to_string()));
+
+        fs::remove_dir_all(offline).unwrap();
+        fs::remove_dir_all(cache_dir).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn http_needs_a_session() {
+        let config = FetchConfig {
+            base_url: BASE_URL.to_string(),
+            session: None,
+            offline_dir: None,
+        };
+        assert!(matches!(config.backend(), Err(AocError::MissingSession)));
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{input::day_name, Answer, AocError};
+use serde::{Deserialize, Serialize};
+
+use crate::registry::{self, Solution};
+
+// Confirmed answers for a day's real input, kept next to it as
+// inputs/<year>/<day>.answers.toml
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    pub fn path(dir: &Path, day: u8) -> PathBuf {
+        dir.join(format!("{}.answers.toml", day_name(day)))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(|error| AocError::Ledger {
+                path: path.display().to_string(),
+                reason: error.to_string(),
+            }),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AocError> {
+        let text = toml::to_string(self).map_err(|error| AocError::Ledger {
+            path: path.display().to_string(),
+            reason: error.to_string(),
+        })?;
+        Ok(fs::write(path, text)?)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part1 = Some(answer.to_string()),
+            2 => self.part2 = Some(answer.to_string()),
+            _ => {}
+        }
+    }
+}
+
+// Store an answer adventofcode.com accepted
+pub fn record(dir: &Path, day: u8, part: u8, answer: &str) -> Result<(), AocError> {
+    let path = Answers::path(dir, day);
+    let mut answers = Answers::load(&path)?;
+    answers.set(part, answer);
+    answers.save(&path)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u8,
+    pub part: u8,
+    pub expected: Answer,
+    // the answer, or the error the solution returned
+    pub actual: Result<Answer, String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} part {}", day_name(self.day), self.part)?;
+        writeln!(f, "  - expected: {}", self.expected)?;
+        match &self.actual {
+            Ok(actual) => write!(f, "  + actual:   {actual}"),
+            Err(error) => write!(f, "  + error:    {error}"),
+        }
+    }
+}
+
+// Run one solution against its input in `dir` and compare with the stored
+// answer. None when there's nothing to compare with.
+pub fn check_solution(dir: &Path, solution: &Solution) -> Result<Option<Mismatch>, AocError> {
+    let answers = Answers::load(&Answers::path(dir, solution.day))?;
+    let Some(expected) = answers.get(solution.part) else {
+        return Ok(None);
+    };
+
+    let input = fs::read_to_string(dir.join(format!("{}.txt", solution.crate_name())))?;
+    let actual = (solution.solve)(&input).map_err(|error| error.to_string());
+
+    let expected = Answer::parse(expected);
+    Ok((actual.as_ref() != Ok(&expected)).then_some(Mismatch {
+        day: solution.day,
+        part: solution.part,
+        expected,
+        actual,
+    }))
+}
+
+pub fn check(dir: &Path) -> Result<Vec<Mismatch>, AocError> {
+    registry::solutions()
+        .iter()
+        .filter_map(|solution| check_solution(dir, solution).transpose())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_common::input::YEAR;
+
+    fn inputs() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../inputs")
+            .join(YEAR.to_string())
+    }
+
+    #[test]
+    fn real_inputs_still_give_the_confirmed_answers() -> miette::Result<()> {
+        let mismatches = check(&inputs())?;
+        assert!(
+            mismatches.is_empty(),
+            "answers changed:\n{}",
+            mismatches
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reports_a_changed_answer() -> miette::Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-golden-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("day-02.txt"),
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        )
+        .unwrap();
+        record(&dir, 2, 1, "2")?;
+
+        let solution = registry::find(2, 1).unwrap();
+        let mismatch = check_solution(&dir, &solution)?.expect("a mismatch");
+        assert_eq!(
+            mismatch.to_string(),
+            "day-02 part 1\n  - expected: 2\n  + actual:   1"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+}
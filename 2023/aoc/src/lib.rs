@@ -0,0 +1,7 @@
+pub mod fetch;
+pub mod golden;
+pub mod registry;
+pub mod submit;
+
+#[cfg(test)]
+mod test_server;
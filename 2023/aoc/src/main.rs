@@ -0,0 +1,199 @@
+use std::{path::PathBuf, time::Instant};
+
+use aoc::{
+    fetch::{FetchConfig, HttpBackend, InputCache},
+    golden,
+    registry::{self, Solution},
+    submit::{Outcome, Submitter, Verdict},
+};
+use aoc_common::{
+    heap,
+    input::{day_name, inputs_dir, InputSource, YEAR},
+    telemetry, Answer, AocError,
+};
+use clap::{ArgAction, Parser, Subcommand};
+use miette::{miette, Context, IntoDiagnostic};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Run Advent of Code 2023 solutions")]
+struct Cli {
+    /// Log what the solutions are doing, -vv for everything (RUST_LOG overrides it)
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day and part, every part of a day, or every day with `all`
+    Run {
+        /// Day number (`3`, `03` or `day-03`), or `all`
+        day: String,
+        /// 1 or 2, both parts when left out
+        part: Option<u8>,
+        /// Puzzle input to use instead of `inputs/2023/<day>.txt`, `-` reads stdin
+        #[arg(short, long)]
+        input: Option<String>,
+    },
+    /// Download a day's input into inputs/2023, unless it's already there
+    Fetch {
+        /// Day number (`3`, `03` or `day-03`)
+        day: String,
+        /// Copy the input from <dir>/2023/<day>.txt instead of adventofcode.com
+        #[arg(long)]
+        offline: Option<PathBuf>,
+    },
+    /// Send an answer to adventofcode.com and keep the verdict in inputs/2023/submissions.toml
+    Submit {
+        /// Day number (`3`, `03` or `day-03`)
+        day: String,
+        /// 1 or 2
+        part: u8,
+        /// The answer to send, worked out by running the solution when left out
+        answer: Option<String>,
+        /// Puzzle input to solve with instead of `inputs/2023/<day>.txt`
+        #[arg(short, long)]
+        input: Option<String>,
+    },
+    /// Check every solution still gives the answers in inputs/2023/<day>.answers.toml
+    Check,
+}
+
+fn parse_day(day: &str) -> miette::Result<Option<u8>> {
+    if day == "all" {
+        return Ok(None);
+    }
+    day.trim_start_matches("day-")
+        .parse::<u8>()
+        .map(Some)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("`{day}` is not a day number"))
+}
+
+fn load(day: u8, input: Option<&str>) -> miette::Result<String> {
+    let day = day_name(day);
+    InputSource::from_arg(input, &day)
+        .load()
+        .wrap_err_with(|| format!("read input for {day}"))
+}
+
+fn solve(solution: &Solution, file: &str) -> miette::Result<Answer> {
+    (solution.solve)(file)
+        .wrap_err_with(|| format!("process {} part {}", solution.crate_name(), solution.part))
+}
+
+fn run(solution: &Solution, file: &str) -> miette::Result<()> {
+    let heap_before = heap::usage();
+    let start = Instant::now();
+    let result = solve(solution, file)?;
+    let elapsed = start.elapsed();
+
+    println!(
+        "{} part {}: {result} ({elapsed:?})",
+        solution.crate_name(),
+        solution.part
+    );
+    if let (Some(before), Some(after)) = (heap_before, heap::usage()) {
+        println!("  heap: {}", after.since(&before));
+    }
+    Ok(())
+}
+
+fn main() -> miette::Result<()> {
+    let cli = Cli::parse();
+    telemetry::init_with_verbosity(cli.verbose);
+    let _heap = heap::profile("aoc");
+
+    match cli.command {
+        Command::Run { day, part, input } => {
+            let day = parse_day(&day)?;
+            let solutions: Vec<Solution> = registry::solutions()
+                .into_iter()
+                .filter(|solution| day.is_none_or(|day| solution.day == day))
+                .filter(|solution| part.is_none_or(|part| solution.part == part))
+                .collect();
+
+            if solutions.is_empty() {
+                return Err(miette!("no solution registered for that day and part"));
+            }
+            if input.is_some() && day.is_none() {
+                return Err(miette!("--input needs a single day"));
+            }
+
+            let mut days: Vec<u8> = solutions.iter().map(|solution| solution.day).collect();
+            days.dedup();
+            for day in days {
+                // read once for both parts, stdin can't be read a second time
+                let file = load(day, input.as_deref())?;
+                for solution in solutions.iter().filter(|solution| solution.day == day) {
+                    run(solution, &file)?;
+                }
+            }
+        }
+        Command::Fetch { day, offline } => {
+            let day = parse_day(&day)?.ok_or_else(|| miette!("fetch needs a single day"))?;
+            let mut config = FetchConfig::from_env(&std::env::current_dir().into_diagnostic()?);
+            config.offline_dir = offline.or(config.offline_dir);
+            let cache = InputCache::in_repo(config.backend()?)?;
+            let input = cache.get(YEAR, day)?;
+            println!(
+                "{} ({} lines)",
+                cache.path(YEAR, day).display(),
+                input.lines().count()
+            );
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            input,
+        } => {
+            let day = parse_day(&day)?.ok_or_else(|| miette!("submit needs a single day"))?;
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let solution = registry::find(day, part)
+                        .ok_or_else(|| miette!("no solution registered for that day and part"))?;
+                    let file = load(day, input.as_deref())?;
+                    solve(&solution, &file)?.to_string()
+                }
+            };
+
+            let cwd = std::env::current_dir().into_diagnostic()?;
+            let config = FetchConfig::from_env(&cwd);
+            let session = config.session.clone().ok_or(AocError::MissingSession)?;
+            let dir = inputs_dir(&cwd).join(YEAR.to_string());
+            let submitter = Submitter::new(
+                dir.join("submissions.toml"),
+                HttpBackend::new(&config.base_url, session),
+            );
+
+            match submitter.submit(YEAR, day, part, &answer)? {
+                Outcome::Verdict(Verdict::Correct) => {
+                    // what `aoc check` and the golden tests compare against
+                    golden::record(&dir, day, part, &answer)?;
+                    println!("{answer} is correct");
+                }
+                Outcome::Verdict(verdict) => println!("{answer} is wrong: {verdict:?}"),
+                Outcome::RateLimited(wait) => {
+                    println!("rate limited, try again in {}s", wait.as_secs())
+                }
+            }
+        }
+        Command::Check => {
+            let dir =
+                inputs_dir(&std::env::current_dir().into_diagnostic()?).join(YEAR.to_string());
+            let mismatches = golden::check(&dir)?;
+            for mismatch in &mismatches {
+                println!("{mismatch}");
+            }
+            if !mismatches.is_empty() {
+                return Err(miette!("{} answers changed", mismatches.len()));
+            }
+            println!("every confirmed answer still matches");
+        }
+    }
+
+    Ok(())
+}
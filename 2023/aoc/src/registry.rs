@@ -0,0 +1,88 @@
+use std::fmt;
+
+use aoc_common::{input::day_name, Answer, AocError};
+
+pub type Solver = fn(&str) -> miette::Result<Answer, AocError>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    pub solve: Solver,
+}
+
+impl Solution {
+    // day-02, the crate (and folder) name for the day
+    pub fn crate_name(&self) -> String {
+        day_name(self.day)
+    }
+}
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} part {}", self.crate_name(), self.part)
+    }
+}
+
+macro_rules! day {
+    ($day:literal, $krate:ident) => {
+        [
+            Solution {
+                day: $day,
+                part: 1,
+                solve: $krate::part1::process,
+            },
+            Solution {
+                day: $day,
+                part: 2,
+                solve: $krate::part2::process,
+            },
+        ]
+    };
+}
+
+// Every day that has a library crate, add new days here after `just create`
+pub fn solutions() -> Vec<Solution> {
+    [
+        day!(1, day_01),
+        day!(2, day_02),
+        day!(3, day_03),
+        day!(4, day_04),
+        day!(5, day_05),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+pub fn find(day: u8, part: u8) -> Option<Solution> {
+    solutions()
+        .into_iter()
+        .find(|solution| solution.day == day && solution.part == part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(2, 1, "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", "1")]
+    #[case(4, 2, "Card 1: 41 48 | 41 86\nCard 2: 13 32 | 61 30", "3")]
+    fn dispatches_to_the_day(
+        #[case] day: u8,
+        #[case] part: u8,
+        #[case] input: &str,
+        #[case] expected: Answer,
+    ) -> miette::Result<()> {
+        let solution = find(day, part).expect("day to be registered");
+        assert_eq!(expected, (solution.solve)(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_day() {
+        assert!(find(25, 1).is_none());
+    }
+}
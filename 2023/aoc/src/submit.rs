@@ -0,0 +1,396 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_common::AocError;
+use serde::{Deserialize, Serialize};
+
+use crate::fetch::HttpBackend;
+
+// What adventofcode.com thought of an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    // the part was already solved, or part 1 isn't done yet
+    WrongLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Verdict(Verdict),
+    RateLimited(Duration),
+}
+
+// You have 1m 23s left to wait / You have 45s left to wait
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    text[start..end]
+        .split_whitespace()
+        .try_fold(0, |seconds, part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value = value.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(seconds + value * 3600),
+                "m" => Some(seconds + value * 60),
+                "s" => Some(seconds + value),
+                _ => None,
+            }
+        })
+        .map(Duration::from_secs)
+}
+
+pub fn parse_response(html: &str) -> Option<Outcome> {
+    if html.contains("That's the right answer") {
+        Some(Outcome::Verdict(Verdict::Correct))
+    } else if html.contains("You gave an answer too recently") {
+        Some(Outcome::RateLimited(
+            parse_wait(html).unwrap_or(Duration::from_secs(60)),
+        ))
+    } else if html.contains("That's not the right answer") {
+        Some(Outcome::Verdict(
+            if html.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if html.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            },
+        ))
+    } else if html.contains("You don't seem to be solving the right level") {
+        Some(Outcome::Verdict(Verdict::WrongLevel))
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+// Every verdict we've had back, so the same wrong answer is never sent twice
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+    // unix seconds, set when the site asks us to slow down
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limited_until: Option<u64>,
+    #[serde(default, rename = "submission")]
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(|error| AocError::Ledger {
+                path: path.display().to_string(),
+                reason: error.to_string(),
+            }),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AocError> {
+        let text = toml::to_string(self).map_err(|error| AocError::Ledger {
+            path: path.display().to_string(),
+            reason: error.to_string(),
+        })?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(fs::write(path, text)?)
+    }
+
+    fn submissions_for(&self, day: u8, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |submission| submission.day == day && submission.part == part)
+    }
+
+    // Ok(Some(verdict)) when the ledger already knows the answer, an error
+    // when sending it would be pointless
+    pub fn check(
+        &self,
+        day: u8,
+        part: u8,
+        answer: &str,
+        now: u64,
+    ) -> Result<Option<Verdict>, AocError> {
+        let refuse = |reason: String| AocError::Refused {
+            answer: answer.to_string(),
+            reason,
+        };
+
+        if let Some(known) = self
+            .submissions_for(day, part)
+            .find(|submission| submission.answer == answer)
+        {
+            return match known.verdict {
+                Verdict::Correct => Ok(Some(Verdict::Correct)),
+                Verdict::WrongLevel => Ok(None),
+                verdict => Err(refuse(format!("it was already marked {verdict:?}"))),
+            };
+        }
+        if let Some(solved) = self
+            .submissions_for(day, part)
+            .find(|submission| submission.verdict == Verdict::Correct)
+        {
+            return Err(refuse(format!("{} was already accepted", solved.answer)));
+        }
+
+        if let Ok(value) = answer.parse::<i64>() {
+            for submission in self.submissions_for(day, part) {
+                let Ok(bound) = submission.answer.parse::<i64>() else {
+                    continue;
+                };
+                match submission.verdict {
+                    Verdict::TooHigh if value >= bound => {
+                        return Err(refuse(format!("{bound} was already too high")))
+                    }
+                    Verdict::TooLow if value <= bound => {
+                        return Err(refuse(format!("{bound} was already too low")))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(until) = self.rate_limited_until.filter(|&until| until > now) {
+            return Err(refuse(format!("rate limited for another {}s", until - now)));
+        }
+        Ok(None)
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, outcome: Outcome, now: u64) {
+        match outcome {
+            Outcome::Verdict(verdict) => {
+                self.rate_limited_until = None;
+                self.submissions.push(Submission {
+                    day,
+                    part,
+                    answer: answer.to_string(),
+                    verdict,
+                });
+            }
+            Outcome::RateLimited(wait) => {
+                self.rate_limited_until = Some(now + wait.as_secs());
+            }
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or(0)
+}
+
+// Somewhere answers can be sent to
+pub trait AnswerEndpoint {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Outcome, AocError>;
+}
+
+impl AnswerEndpoint for HttpBackend {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Outcome, AocError> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url());
+        let http_error = |reason: String| AocError::Http {
+            url: url.clone(),
+            reason,
+        };
+
+        let level = part.to_string();
+        let html = self
+            .agent()
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", level.as_str()), ("answer", answer)])
+            .map_err(|error| http_error(error.to_string()))?
+            .into_string()
+            .map_err(|error| http_error(error.to_string()))?;
+
+        parse_response(&html).ok_or_else(|| http_error("unrecognised response page".to_string()))
+    }
+}
+
+// Checks the ledger, sends the answer when that's worth doing and writes
+// whatever came back to the ledger at `ledger_path`
+pub struct Submitter<E> {
+    pub ledger_path: PathBuf,
+    pub endpoint: E,
+}
+
+impl<E: AnswerEndpoint> Submitter<E> {
+    pub fn new(ledger_path: impl Into<PathBuf>, endpoint: E) -> Self {
+        Submitter {
+            ledger_path: ledger_path.into(),
+            endpoint,
+        }
+    }
+
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Outcome, AocError> {
+        let mut ledger = Ledger::load(&self.ledger_path)?;
+        if let Some(verdict) = ledger.check(day, part, answer, now())? {
+            return Ok(Outcome::Verdict(verdict));
+        }
+
+        let outcome = self.endpoint.submit(year, day, part, answer)?;
+        ledger.record(day, part, answer, outcome, now());
+        ledger.save(&self.ledger_path)?;
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    use crate::test_server::StubServer;
+
+    #[rstest]
+    #[case(
+        "<article><p>That's the right answer! You are one gold star closer.</p></article>",
+        Outcome::Verdict(Verdict::Correct)
+    )]
+    #[case(
+        "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        Outcome::Verdict(Verdict::TooHigh)
+    )]
+    #[case(
+        "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        Outcome::Verdict(Verdict::TooLow)
+    )]
+    #[case(
+        "<article><p>That's not the right answer. If you're stuck...</p></article>",
+        Outcome::Verdict(Verdict::Incorrect)
+    )]
+    #[case(
+        "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.</p></article>",
+        Outcome::RateLimited(Duration::from_secs(83))
+    )]
+    #[case(
+        "<article><p>You gave an answer too recently. You have 45s left to wait.</p></article>",
+        Outcome::RateLimited(Duration::from_secs(45))
+    )]
+    #[case(
+        "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>",
+        Outcome::Verdict(Verdict::WrongLevel)
+    )]
+    fn parses_verdicts(#[case] html: &str, #[case] expected: Outcome) {
+        assert_eq!(Some(expected), parse_response(html));
+    }
+
+    fn ledger() -> Ledger {
+        let mut ledger = Ledger::default();
+        ledger.record(3, 1, "600000", Outcome::Verdict(Verdict::TooHigh), 0);
+        ledger.record(3, 1, "500000", Outcome::Verdict(Verdict::TooLow), 0);
+        ledger.record(3, 1, "527000", Outcome::Verdict(Verdict::Incorrect), 0);
+        ledger.record(4, 1, "21088", Outcome::Verdict(Verdict::Correct), 0);
+        ledger
+    }
+
+    #[rstest]
+    #[case(3, 1, "527000", "already marked Incorrect")]
+    #[case(3, 1, "600001", "600000 was already too high")]
+    #[case(3, 1, "499999", "500000 was already too low")]
+    #[case(4, 1, "21089", "21088 was already accepted")]
+    fn refuses_known_bad_answers(
+        #[case] day: u8,
+        #[case] part: u8,
+        #[case] answer: &str,
+        #[case] reason: &str,
+    ) {
+        match ledger().check(day, part, answer, 0) {
+            Err(AocError::Refused { reason: given, .. }) => assert!(
+                given.contains(reason),
+                "`{given}` should mention `{reason}`"
+            ),
+            other => panic!("expected {answer} to be refused, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn allows_answers_inside_the_bounds() -> miette::Result<()> {
+        let ledger = ledger();
+        assert_eq!(ledger.check(3, 1, "527369", 0)?, None);
+        assert_eq!(ledger.check(4, 1, "21088", 0)?, Some(Verdict::Correct));
+        assert_eq!(ledger.check(3, 2, "600001", 0)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn waits_out_the_rate_limit() -> miette::Result<()> {
+        let mut ledger = Ledger::default();
+        ledger.record(
+            1,
+            1,
+            "1",
+            Outcome::RateLimited(Duration::from_secs(30)),
+            100,
+        );
+        assert!(ledger.check(1, 1, "1", 110).is_err());
+        assert_eq!(ledger.check(1, 1, "1", 130)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn submits_and_records_the_verdict() -> miette::Result<()> {
+        let server = StubServer::start(|request| {
+            if request.body.contains("answer=100") {
+                (
+                    200,
+                    "<p>That's not the right answer; your answer is too high.</p>".to_string(),
+                )
+            } else {
+                (200, "<p>That's the right answer!</p>".to_string())
+            }
+        });
+        let dir = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let ledger_path = dir.join("submissions.toml");
+        let submitter = Submitter::new(
+            &ledger_path,
+            HttpBackend::new(&server.base_url, "abc123".to_string()),
+        );
+
+        assert_eq!(
+            submitter.submit(2023, 2, 1, "100")?,
+            Outcome::Verdict(Verdict::TooHigh)
+        );
+        assert!(submitter.submit(2023, 2, 1, "150").is_err());
+        assert_eq!(
+            submitter.submit(2023, 2, 1, "42")?,
+            Outcome::Verdict(Verdict::Correct)
+        );
+        // already known, nothing is sent
+        assert_eq!(
+            submitter.submit(2023, 2, 1, "42")?,
+            Outcome::Verdict(Verdict::Correct)
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].url, "/2023/day/2/answer");
+        assert_eq!(requests[0].body, "level=1&answer=100");
+
+        let ledger = Ledger::load(&ledger_path)?;
+        assert_eq!(ledger.submissions.len(), 2);
+        assert_eq!(ledger.submissions[1].verdict, Verdict::Correct);
+
+        fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use tiny_http::{Response, Server};
+
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    pub url: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+// A local stand-in for adventofcode.com that answers each request with
+// `respond` and keeps a copy of everything it was sent
+pub struct StubServer {
+    pub base_url: String,
+    pub requests: Arc<Mutex<Vec<Recorded>>>,
+    handle: Option<JoinHandle<()>>,
+    server: Arc<Server>,
+}
+
+impl StubServer {
+    pub fn start(respond: impl Fn(&Recorded) -> (u16, String) + Send + 'static) -> Self {
+        let server = Arc::new(Server::http("127.0.0.1:0").expect("stub server to bind"));
+        let base_url = format!("http://{}", server.server_addr());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let handle = {
+            let server = Arc::clone(&server);
+            let requests = Arc::clone(&requests);
+            thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let mut body = String::new();
+                    let _ = request.as_reader().read_to_string(&mut body);
+                    let recorded = Recorded {
+                        method: request.method().to_string(),
+                        url: request.url().to_string(),
+                        cookie: request
+                            .headers()
+                            .iter()
+                            .find(|header| header.field.equiv("Cookie"))
+                            .map(|header| header.value.to_string()),
+                        body,
+                    };
+                    let (status, reply) = respond(&recorded);
+                    requests.lock().unwrap().push(recorded);
+                    let _ = request.respond(Response::from_string(reply).with_status_code(status));
+                }
+            })
+        };
+
+        StubServer {
+            base_url,
+            requests,
+            handle: Some(handle),
+            server,
+        }
+    }
+
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for StubServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
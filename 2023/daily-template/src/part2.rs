@@ -1,7 +1,9 @@
 use crate::custom_error::AocError;
+use aoc_common::Answer;
 
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    Ok("todo - part 2".to_string())
+#[tracing::instrument(skip_all)]
+pub fn process(_input: &str) -> miette::Result<Answer, AocError> {
+    Ok(Answer::from("todo - part 2"))
 }
 
 #[cfg(test)]
@@ -13,7 +15,7 @@ mod tests {
     #[rstest]
     #[case("", "")]
 
-    fn tests(#[case] input: &str, #[case] expected: String) -> miette::Result<()> {
+    fn tests(#[case] input: &str, #[case] expected: Answer) -> miette::Result<()> {
         assert_eq!(expected, process(input)?);
         Ok(())
     }
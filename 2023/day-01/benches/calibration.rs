@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use aho_corasick::{AhoCorasick, PatternID};
+use aoc_common::input::{conventional_path, YEAR};
+use day_01::{
+    calibration::{self, DigitlessLines},
+    scanner::CalibrationScanner,
+    vocabulary::DigitVocabulary,
+};
+use divan::Bencher;
+
+fn main() {
+    divan::main();
+}
+
+const EXAMPLE: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+// The real input when there is one, the example repeated up to about its
+// size when there isn't
+fn input() -> String {
+    let path = conventional_path(Path::new(env!("CARGO_MANIFEST_DIR")), YEAR, "day-01");
+    std::fs::read_to_string(path).unwrap_or_else(|_| vec![EXAMPLE; 150].join("\n"))
+}
+
+// How find_number used to work: a new automaton for every line and every
+// overlapping match collected, to keep the first and the last
+fn automaton_per_line(line: &str) -> u32 {
+    let patterns = &[
+        "1", "2", "3", "4", "5", "6", "7", "8", "9", "one", "two", "three", "four", "five", "six",
+        "seven", "eight", "nine",
+    ];
+    let ac = AhoCorasick::new(patterns).unwrap();
+    let results: Vec<PatternID> = ac
+        .find_overlapping_iter(line)
+        .map(|mat| mat.pattern())
+        .collect();
+    let (Some(first), Some(last)) = (results.first(), results.last()) else {
+        return 0;
+    };
+    // patterns 0-8 are 1-9 and 9-17 are one-nine
+    let digit = |pattern: &PatternID| pattern.as_u32() % 9 + 1;
+    digit(first) * 10 + digit(last)
+}
+
+#[divan::bench]
+fn per_line_automaton(bencher: Bencher) {
+    let input = input();
+    bencher.bench(|| {
+        divan::black_box(&input)
+            .split('\n')
+            .map(automaton_per_line)
+            .sum::<u32>()
+    });
+}
+
+// The automaton is built once up front, only the scan is timed
+#[divan::bench]
+fn scanner(bencher: Bencher) {
+    let input = input();
+    let scanner = CalibrationScanner::new(&DigitVocabulary::english());
+    bencher.bench(|| {
+        let input = divan::black_box(&input);
+        let values = scanner.lines(input.as_bytes()).map(|(_, value)| value);
+        calibration::total(input, values, DigitlessLines::CountAsZero).unwrap()
+    });
+}
@@ -0,0 +1,117 @@
+use aoc_common::{answer::checked_sum, report::line_spans, AocError, InputReport};
+
+// What to do with a calibration line that has no digit in it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DigitlessLines {
+    // fail with the line numbers of every one of them
+    #[default]
+    Report,
+    // count them as zero, like the first version did
+    CountAsZero,
+}
+
+// Adds up the calibration value of every line, `values` having one for each
+// line of `input` split on '\n'. The empty lines that splitting leaves after
+// the final newline aren't part of the document, those are dropped in either
+// mode. A blank line anywhere else is a line without digits.
+pub fn total(
+    input: &str,
+    values: impl IntoIterator<Item = Option<u32>>,
+    digitless: DigitlessLines,
+) -> Result<u64, AocError> {
+    let lines = input.trim_end_matches(['\n', '\r']).lines().count();
+    let mut missing = Vec::new();
+    let total = checked_sum(
+        values
+            .into_iter()
+            .take(lines)
+            .enumerate()
+            .map(|(line, value)| {
+                value.unwrap_or_else(|| {
+                    missing.push(line);
+                    0
+                })
+            }),
+    )?;
+
+    if missing.is_empty() {
+        return Ok(total);
+    }
+    let numbers: Vec<usize> = missing.iter().map(|line| line + 1).collect();
+    match digitless {
+        DigitlessLines::CountAsZero => {
+            tracing::warn!(lines = ?numbers, "counted lines without digits as zero");
+            Ok(total)
+        }
+        DigitlessLines::Report => Err(report(input, &missing)),
+    }
+}
+
+fn report(input: &str, missing: &[usize]) -> AocError {
+    let message = match missing.len() {
+        1 => "a line has no digits".to_string(),
+        count => format!("{count} lines have no digits"),
+    };
+    let mut report = InputReport::new(message, input)
+        .with_note("pass --lenient to count lines without digits as zero");
+    let mut missing = missing.iter().peekable();
+    for (line, span) in line_spans(input) {
+        if missing.next_if_eq(&&line).is_some() {
+            report = report.with_label(span, format!("line {}", line + 1));
+        }
+    }
+    report.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn values(input: &str) -> impl Iterator<Item = Option<u32>> + '_ {
+        input.split('\n').map(|line| line.trim_end().parse().ok())
+    }
+
+    #[rstest]
+    #[case("12\n34", 46)]
+    #[case("12\n34\n", 46)]
+    #[case("12\r\n34\r\n\n\n", 46)]
+    #[case("", 0)]
+    #[case("\n", 0)]
+    fn trailing_empty_lines_are_not_lines(#[case] input: &str, #[case] expected: u64) {
+        assert_eq!(
+            total(input, values(input), DigitlessLines::Report).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn digitless_lines_are_reported_by_number() {
+        let input = "12\nabc\n\n34\nx\n";
+        let Err(AocError::InvalidInput(report)) =
+            total(input, values(input), DigitlessLines::Report)
+        else {
+            panic!("expected the lines without digits to be reported");
+        };
+        assert_eq!(report.message, "3 lines have no digits");
+        let labels: Vec<_> = report
+            .labels
+            .iter()
+            .map(|(span, label)| (&input[span.clone()], label.as_str()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![("abc", "line 2"), ("", "line 3"), ("x", "line 5")]
+        );
+    }
+
+    #[test]
+    fn lenient_counts_them_as_zero() {
+        let input = "12\nabc\n\n34\nx\n";
+        assert_eq!(
+            total(input, values(input), DigitlessLines::CountAsZero).unwrap(),
+            46
+        );
+    }
+}
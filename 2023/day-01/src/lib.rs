@@ -0,0 +1,7 @@
+pub use aoc_common::custom_error;
+
+pub mod calibration;
+pub mod part1;
+pub mod part2;
+pub mod scanner;
+pub mod vocabulary;
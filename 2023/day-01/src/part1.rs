@@ -0,0 +1,73 @@
+use crate::{calibration, calibration::DigitlessLines, custom_error::AocError};
+use aoc_common::Answer;
+
+fn find_number(input: &str) -> Option<u32> {
+    // go to the first digit closest to the first char forward and the first digit closest to
+    // the last char backwards and concantenate them together
+
+    let mut number = input.chars().filter_map(|c| c.to_digit(10));
+    let first = number.next()?;
+    let last = number.next_back().unwrap_or(first);
+    tracing::trace!(line = input, first, last);
+
+    Some(first * 10 + last)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn process(input: &str) -> miette::Result<Answer, AocError> {
+    process_with(input, DigitlessLines::default())
+}
+
+pub fn process_with(input: &str, digitless: DigitlessLines) -> miette::Result<Answer, AocError> {
+    // split by newline into vec of lines
+    let values = input.split('\n').map(find_number);
+    Ok(calibration::total(input, values, digitless)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1abc2", 12)]
+    #[case("pqr3stu8vwx", 38)]
+    #[case("a1b2c3d4e5f", 15)]
+    #[case("treb7uchet", 77)]
+    #[case("z5", 55)]
+    fn check_single_line(#[case] line: &str, #[case] expected: u32) {
+        assert_eq!(Some(expected), find_number(line));
+    }
+
+    #[rstest]
+    #[case("abc")]
+    #[case("")]
+    fn no_digits(#[case] line: &str) {
+        assert_eq!(None, find_number(line));
+    }
+
+    #[rstest]
+    #[case(
+        "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet",
+        "142"
+    )]
+    fn tests(#[case] input: &str, #[case] expected: Answer) -> miette::Result<()> {
+        assert_eq!(expected, process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn digitless_lines_fail_unless_lenient() -> miette::Result<()> {
+        let input = "1abc2\nabc\ntreb7uchet\n";
+        assert!(matches!(process(input), Err(AocError::InvalidInput(_))));
+        assert_eq!(
+            Answer::Unsigned(89),
+            process_with(input, DigitlessLines::CountAsZero)?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    calibration::{self, DigitlessLines},
+    custom_error::AocError,
+    scanner::CalibrationScanner,
+    vocabulary::DigitVocabulary,
+};
+use aoc_common::Answer;
+
+// have to find first number(digit or spelled out) from the front and first digit from the back
+fn find_number(scanner: &CalibrationScanner, line: &[u8]) -> Option<u32> {
+    let value = scanner.calibration_value(line);
+    tracing::trace!(line = %line.escape_ascii(), ?value);
+    value
+}
+
+#[tracing::instrument(skip_all)]
+pub fn process(input: &str) -> miette::Result<Answer, AocError> {
+    process_with(
+        input,
+        &DigitVocabulary::english(),
+        DigitlessLines::default(),
+    )
+}
+
+// The same calibration document with the digits spelled out some other way
+pub fn process_with(
+    input: &str,
+    vocabulary: &DigitVocabulary,
+    digitless: DigitlessLines,
+) -> miette::Result<Answer, AocError> {
+    let scanner = CalibrationScanner::new(vocabulary);
+    let values = input
+        .as_bytes()
+        .split(|byte| *byte == b'\n')
+        .map(|line| find_number(&scanner, line));
+    Ok(calibration::total(input, values, digitless)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("a1b2c3d4e5f", 15)]
+    #[case("z5", 55)]
+    #[case("one", 11)]
+    // overlapping words share a letter, both count
+    #[case("sevenine", 79)]
+    #[case("eighthree", 83)]
+    fn check_single_line(#[case] line: &str, #[case] expected: u32) {
+        let scanner = CalibrationScanner::new(&DigitVocabulary::english());
+        assert_eq!(Some(expected), find_number(&scanner, line.as_bytes()));
+    }
+
+    #[rstest]
+    #[case(
+        "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen",
+        // 29+83+13+24+42+14+76=281
+        "281"
+    )]
+    fn check_for_word_numbers(#[case] input: &str, #[case] expected: Answer) -> miette::Result<()> {
+        assert_eq!(expected, process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn spelled_out_in_german() -> miette::Result<()> {
+        let input = "zwei1neun
+achtzweidrei
+abceins2dreixyz";
+        // 29+83+13=125
+        assert_eq!(
+            Answer::Unsigned(125),
+            process_with(input, &DigitVocabulary::german(), DigitlessLines::Report)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn digitless_lines_fail_unless_lenient() -> miette::Result<()> {
+        let input = "two1nine\nxyz\n7pqrstsixteen\n";
+        let english = DigitVocabulary::english();
+        assert!(matches!(process(input), Err(AocError::InvalidInput(_))));
+        assert_eq!(
+            Answer::Unsigned(105),
+            process_with(input, &english, DigitlessLines::CountAsZero)?
+        );
+        Ok(())
+    }
+}
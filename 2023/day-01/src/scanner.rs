@@ -0,0 +1,121 @@
+use aho_corasick::{AhoCorasick, Anchored, Input, MatchKind, StartKind};
+
+use crate::vocabulary::DigitVocabulary;
+
+// Finds the first and last digit of calibration lines. The automaton is
+// built once for the vocabulary and a line is never copied or collected:
+// the first digit is the leftmost match, the last is the first anchored
+// match found walking back from the end of the line.
+#[derive(Debug, Clone)]
+pub struct CalibrationScanner {
+    automaton: AhoCorasick,
+    digits: Vec<u32>,
+}
+
+impl CalibrationScanner {
+    pub fn new(vocabulary: &DigitVocabulary) -> Self {
+        let (patterns, digits): (Vec<&str>, Vec<u32>) = vocabulary.patterns().unzip();
+        // Both, so the same automaton can do the unanchored search for the
+        // first digit and the anchored ones for the last
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .start_kind(StartKind::Both)
+            .build(patterns)
+            .expect("digit patterns are small enough");
+        CalibrationScanner { automaton, digits }
+    }
+
+    // The digit spelled out starting exactly at `start`, if any
+    fn digit_at(&self, line: &[u8], start: usize) -> Option<u32> {
+        let input = Input::new(line)
+            .span(start..line.len())
+            .anchored(Anchored::Yes);
+        self.automaton
+            .find(input)
+            .map(|found| self.digits[found.pattern()])
+    }
+
+    // First digit and last digit as a two digit number. Words can overlap
+    // ("eighthree" is 83), the last digit is the one starting furthest right.
+    pub fn calibration_value(&self, line: &[u8]) -> Option<u32> {
+        let first = self.automaton.find(line)?;
+        // the first match is there to fall back on, so this always finds one
+        let last = (first.start()..line.len())
+            .rev()
+            .find_map(|start| self.digit_at(line, start))?;
+        Some(self.digits[first.pattern()] * 10 + last)
+    }
+
+    // Every line of the input with its calibration value, split straight off
+    // the bytes
+    pub fn lines<'a>(
+        &'a self,
+        input: &'a [u8],
+    ) -> impl Iterator<Item = (&'a [u8], Option<u32>)> + 'a {
+        input
+            .split(|byte| *byte == b'\n')
+            .map(|line| (line, self.calibration_value(line)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(DigitVocabulary::english(), "eighthree", 83)]
+    #[case(DigitVocabulary::english(), "xtwone3four", 24)]
+    #[case(DigitVocabulary::german(), "xfünfzweiundsiebenx", 57)]
+    #[case(DigitVocabulary::german(), "achteinsechs", 86)]
+    #[case(DigitVocabulary::spanish(), "dosiete", 27)]
+    #[case(DigitVocabulary::spanish(), "4cuatrocinco", 45)]
+    #[case(DigitVocabulary::french(), "cinquatre", 54)]
+    #[case(DigitVocabulary::french(), "neuf2huit", 98)]
+    fn builtin_vocabularies(
+        #[case] vocabulary: DigitVocabulary,
+        #[case] line: &str,
+        #[case] expected: u32,
+    ) {
+        assert_eq!(
+            CalibrationScanner::new(&vocabulary).calibration_value(line.as_bytes()),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn last_digit_starts_furthest_right() {
+        // `elve` sits inside `twelve` and ends with it, but starts later
+        let scanner = CalibrationScanner::new(&DigitVocabulary::new([("twelve", 2), ("elve", 5)]));
+        assert_eq!(scanner.calibration_value(b"twelve"), Some(25));
+        assert_eq!(scanner.calibration_value(b"xelve"), Some(55));
+    }
+
+    #[test]
+    fn zero_only_when_asked_for() {
+        let line = b"zero5nulzero";
+        let without = CalibrationScanner::new(&DigitVocabulary::english());
+        assert_eq!(without.calibration_value(line), Some(55));
+        let with = CalibrationScanner::new(&DigitVocabulary::english().with_zero());
+        assert_eq!(with.calibration_value(line), Some(0));
+        assert_eq!(with.calibration_value(b"a0b7"), Some(7));
+    }
+
+    #[test]
+    fn no_digits_at_all() {
+        let scanner = CalibrationScanner::new(&DigitVocabulary::english());
+        assert_eq!(scanner.calibration_value(b"abcdef"), None);
+        assert_eq!(scanner.calibration_value(b""), None);
+    }
+
+    #[test]
+    fn lines_of_the_whole_input() {
+        let scanner = CalibrationScanner::new(&DigitVocabulary::english());
+        let values: Vec<_> = scanner
+            .lines(b"two1nine\nabc\n7pqrstsixteen\n")
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(values, vec![Some(29), None, Some(76), None]);
+    }
+}
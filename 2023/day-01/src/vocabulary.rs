@@ -0,0 +1,195 @@
+use std::{fs, path::Path};
+
+use aoc_common::{report::line_spans, AocError, InputReport};
+
+// Spelled out digits to look for alongside 1-9. Words for zero are only
+// matched (together with a plain `0`) after `with_zero`, the puzzle itself
+// never has them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitVocabulary {
+    words: Vec<(String, u32)>,
+    zero: bool,
+}
+
+impl DigitVocabulary {
+    pub fn new<W: Into<String>>(words: impl IntoIterator<Item = (W, u32)>) -> Self {
+        DigitVocabulary {
+            words: words
+                .into_iter()
+                .map(|(word, digit)| (word.into(), digit))
+                .collect(),
+            zero: false,
+        }
+    }
+
+    // zero first, so the index is the digit
+    fn numbered(words: [&str; 10]) -> Self {
+        DigitVocabulary::new(words.into_iter().zip(0..))
+    }
+
+    pub fn english() -> Self {
+        DigitVocabulary::numbered([
+            "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ])
+    }
+
+    pub fn german() -> Self {
+        DigitVocabulary::numbered([
+            "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+        ])
+    }
+
+    pub fn spanish() -> Self {
+        DigitVocabulary::numbered([
+            "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+        ])
+    }
+
+    pub fn french() -> Self {
+        DigitVocabulary::numbered([
+            "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+        ])
+    }
+
+    pub fn builtin(language: &str) -> Option<Self> {
+        match language {
+            "english" => Some(DigitVocabulary::english()),
+            "german" => Some(DigitVocabulary::german()),
+            "spanish" => Some(DigitVocabulary::spanish()),
+            "french" => Some(DigitVocabulary::french()),
+            _ => None,
+        }
+    }
+
+    // What --words names: a built-in language or a word list file
+    pub fn from_arg(arg: &str) -> Result<Self, AocError> {
+        if let Some(vocabulary) = DigitVocabulary::builtin(arg) {
+            return Ok(vocabulary);
+        }
+        let path = Path::new(arg);
+        if path.is_file() {
+            return DigitVocabulary::load(path);
+        }
+        Err(InputReport::new("unknown digit words", arg)
+            .with_label(
+                0..arg.len(),
+                "not english, german, spanish, french or a word list file",
+            )
+            .into())
+    }
+
+    pub fn with_zero(mut self) -> Self {
+        self.zero = true;
+        self
+    }
+
+    // One `word digit` pair per line, blank lines and `#` comments are skipped:
+    //
+    //   # dutch
+    //   een 1
+    //   twee 2
+    pub fn parse(text: &str) -> Result<Self, AocError> {
+        let mut words = Vec::new();
+        let mut report = InputReport::new("not a `word digit` pair", text);
+        for (_, span) in line_spans(text) {
+            let line = text[span.clone()].trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let pair = line
+                .split_once(char::is_whitespace)
+                .and_then(|(word, digit)| {
+                    let digit = digit
+                        .trim()
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|digit| *digit < 10)?;
+                    Some((word.to_string(), digit))
+                });
+            match pair {
+                Some(pair) => words.push(pair),
+                None => report = report.with_label(span, "expected a word and a digit from 0 to 9"),
+            }
+        }
+
+        if !report.labels.is_empty() {
+            return Err(report.into());
+        }
+        Ok(DigitVocabulary::new(words))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        DigitVocabulary::parse(&fs::read_to_string(path)?)
+    }
+
+    // Everything to search for and the digit it stands for, digits included
+    pub fn patterns(&self) -> impl Iterator<Item = (&str, u32)> + '_ {
+        const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+        DIGITS
+            .iter()
+            .zip(0..)
+            .map(|(digit, value)| (*digit, value))
+            .chain(
+                self.words
+                    .iter()
+                    .map(|(word, digit)| (word.as_str(), *digit)),
+            )
+            .filter(|(_, digit)| self.zero || *digit != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::scanner::CalibrationScanner;
+
+    #[test]
+    fn custom_words_from_a_file() -> miette::Result<()> {
+        let path = std::env::temp_dir().join(format!("day-01-words-{}.txt", std::process::id()));
+        fs::write(&path, "# dutch\neen 1\ntwee 2\n\nnegen 9\nnul 0\n").unwrap();
+        let vocabulary = DigitVocabulary::load(&path)?;
+        fs::remove_file(&path).unwrap();
+
+        let scanner = CalibrationScanner::new(&vocabulary);
+        assert_eq!(scanner.calibration_value(b"tweenegeneen"), Some(21));
+        assert_eq!(scanner.calibration_value(b"nul"), None);
+        let scanner = CalibrationScanner::new(&vocabulary.with_zero());
+        assert_eq!(scanner.calibration_value(b"nul3"), Some(3));
+        Ok(())
+    }
+
+    #[test]
+    fn words_by_language_or_file() -> miette::Result<()> {
+        assert_eq!(
+            DigitVocabulary::from_arg("german")?,
+            DigitVocabulary::german()
+        );
+
+        let path = std::env::temp_dir().join(format!("day-01-arg-{}.txt", std::process::id()));
+        fs::write(&path, "een 1\n").unwrap();
+        let loaded = DigitVocabulary::from_arg(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded?, DigitVocabulary::new([("een", 1)]));
+
+        assert!(matches!(
+            DigitVocabulary::from_arg("klingon"),
+            Err(AocError::InvalidInput(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_word_lists_point_at_the_line() {
+        let Err(AocError::InvalidInput(report)) = DigitVocabulary::parse("een 1\ntwee\ndrie 12\n")
+        else {
+            panic!("expected the bad lines to be reported");
+        };
+        let labelled: Vec<_> = report
+            .labels
+            .iter()
+            .map(|(span, _)| &report.src[span.clone()])
+            .collect();
+        assert_eq!(labelled, vec!["twee", "drie 12"]);
+    }
+}
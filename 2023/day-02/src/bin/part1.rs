@@ -1,9 +1,26 @@
-use day_02::part1::process;
-use miette::Context;
+use std::path::Path;
+
+use aoc_common::{
+    heap,
+    input::{flag_value, read_input},
+    telemetry,
+};
+use day_02::{cube_set::CubeSet, part1::process_with};
+use miette::{bail, Context};
 
 fn main() -> miette::Result<()> {
-    let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    telemetry::init();
+    let _heap = heap::profile(env!("CARGO_PKG_NAME"));
+
+    // --bag="12 red, 13 green, 14 blue" or --bag-file=<path> to ask about another bag
+    let bag = match (flag_value("bag"), flag_value("bag-file")) {
+        (Some(_), Some(_)) => bail!("pass either --bag or --bag-file, not both"),
+        (Some(cubes), None) => CubeSet::parse(&cubes).context("read --bag")?,
+        (None, Some(path)) => CubeSet::load(Path::new(&path)).context("read --bag-file")?,
+        (None, None) => CubeSet::default(),
+    };
+    let file = read_input(env!("CARGO_PKG_NAME")).context("read input")?;
+    let result = process_with(&file, &bag).context("process part 1")?;
     println!("{}", result);
     Ok(())
 }
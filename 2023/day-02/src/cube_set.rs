@@ -0,0 +1,150 @@
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use aoc_common::{report::line_spans, AocError, InputReport};
+
+// How many cubes of each color are in the bag. A color that isn't listed
+// isn't in the bag at all, so asking about it gives zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeSet {
+    cubes: BTreeMap<String, u32>,
+}
+
+impl CubeSet {
+    pub fn new<C: Into<String>>(cubes: impl IntoIterator<Item = (C, u32)>) -> Self {
+        CubeSet {
+            cubes: cubes
+                .into_iter()
+                .map(|(color, amount)| (color.into(), amount))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    // Written the way the games show their cubes, `12 red, 13 green, 14 blue`.
+    // In a file they can be split over lines, blank lines and `#` comments
+    // are skipped.
+    pub fn parse(text: &str) -> Result<Self, AocError> {
+        let mut cubes = BTreeMap::new();
+        let mut report = InputReport::new("not a `<amount> <color>` list", text);
+        for (_, span) in line_spans(text) {
+            let line = &text[span.clone()];
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let mut start = span.start;
+            for entry in line.split(',') {
+                let lead = entry.len() - entry.trim_start().len();
+                let entry_span = start + lead..start + entry.trim_end().len();
+                start += entry.len() + 1;
+
+                // any spacing between the two, like the games allow
+                let mut words = entry.split_whitespace();
+                let parsed = match (words.next(), words.next(), words.next()) {
+                    (Some(amount), Some(color), None) if color.chars().all(char::is_alphabetic) => {
+                        amount.parse::<u32>().ok().map(|amount| (amount, color))
+                    }
+                    _ => None,
+                };
+                match parsed {
+                    Some((_, color)) if cubes.contains_key(color) => {
+                        report =
+                            report.with_label(entry_span, format!("{color} is already in the bag"))
+                    }
+                    Some((amount, color)) => {
+                        cubes.insert(color.to_string(), amount);
+                    }
+                    None => {
+                        report = report.with_label(entry_span, "expected an amount and a color")
+                    }
+                }
+            }
+        }
+
+        if !report.labels.is_empty() {
+            return Err(report.into());
+        }
+        Ok(CubeSet { cubes })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        CubeSet::parse(&fs::read_to_string(path)?)
+    }
+}
+
+// The bag the elf asks about in part 1
+impl Default for CubeSet {
+    fn default() -> Self {
+        CubeSet::new([("red", 12), ("green", 13), ("blue", 14)])
+    }
+}
+
+impl fmt::Display for CubeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (color, amount)) in self.cubes.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{amount} {color}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("12 red, 13 green, 14 blue", CubeSet::default())]
+    #[case("# the usual bag\n12 red,13 green\n\n  14 blue\n", CubeSet::default())]
+    #[case("3 purple", CubeSet::new([("purple", 3)]))]
+    #[case("12\tred,13   green\n14 \t blue", CubeSet::default())]
+    #[case("", CubeSet::new::<&str>([]))]
+    fn parses_like_a_round(#[case] text: &str, #[case] expected: CubeSet) {
+        assert_eq!(CubeSet::parse(text).unwrap(), expected);
+    }
+
+    #[test]
+    fn colors_not_in_the_bag_are_zero() {
+        let bag = CubeSet::default();
+        assert_eq!(bag.get("green"), 13);
+        assert_eq!(bag.get("purple"), 0);
+    }
+
+    #[test]
+    fn bad_entries_point_at_themselves() {
+        let text = "12 red, thirteen green\n14 blue, 2 red, 4\n";
+        let Err(AocError::InvalidInput(report)) = CubeSet::parse(text) else {
+            panic!("expected the bad entries to be reported");
+        };
+        let labels: Vec<_> = report
+            .labels
+            .iter()
+            .map(|(span, label)| (&text[span.clone()], label.as_str()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("thirteen green", "expected an amount and a color"),
+                ("2 red", "red is already in the bag"),
+                ("4", "expected an amount and a color"),
+            ]
+        );
+    }
+
+    #[test]
+    fn loads_from_a_file() -> miette::Result<()> {
+        let path = std::env::temp_dir().join(format!("day-02-bag-{}.txt", std::process::id()));
+        fs::write(&path, "20 red\n1 yellow\n").unwrap();
+        let bag = CubeSet::load(&path)?;
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(bag.to_string(), "20 red, 1 yellow");
+        Ok(())
+    }
+}
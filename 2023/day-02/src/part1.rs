@@ -1,12 +1,16 @@
-use crate::custom_error::AocError;
+use crate::{cube_set::CubeSet, custom_error::AocError};
+use aoc_common::{
+    answer::checked_sum,
+    parse::{key_value, labelled, lines, padded, parse_all, unsigned},
+    Answer,
+};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{self, alpha1, digit1, line_ending},
+    character::complete::{alpha1, space1},
     multi::separated_list1,
-    sequence::{preceded, separated_pair},
+    sequence::separated_pair,
     IResult,
 };
-use std::{collections::BTreeMap, ops::Not};
+use std::ops::Not;
 
 #[derive(Debug)]
 struct Cube<'a> {
@@ -16,65 +20,70 @@ struct Cube<'a> {
 
 #[derive(Debug)]
 struct Game<'a> {
-    id: &'a str,
+    id: u32,
     rounds: Vec<Vec<Cube<'a>>>,
 }
 
 impl<'a> Game<'a> {
-    fn valid_for_cube_set(&self, map: &BTreeMap<&str, u32>) -> Option<u32> {
-        self.rounds
+    // a color the bag doesn't have only fits if none of it was shown
+    fn valid_for_cube_set(&self, bag: &CubeSet) -> Option<u32> {
+        let possible = self
+            .rounds
             .iter()
             .any(|round| {
-                round.iter().any(|shown_cube| {
-                    shown_cube.amount > *map.get(shown_cube.color).expect("a valid cube")
-                })
+                round
+                    .iter()
+                    .any(|shown_cube| shown_cube.amount > bag.get(shown_cube.color))
             })
-            .not()
-            .then_some(
-                self.id
-                    .parse::<u32>()
-                    .expect("game id should a parsable u32"),
-            )
+            .not();
+        tracing::debug!(game = self.id, possible);
+
+        possible.then_some(self.id)
     }
 }
 
 // 4 red
-fn cube(input: &str) -> IResult<&str, Cube> {
-    let (input, (amount, color)) = separated_pair(complete::u32, tag(" "), alpha1)(input)?;
+fn cube(input: &str) -> IResult<&str, Cube<'_>> {
+    let (input, (amount, color)) = separated_pair(unsigned, space1, alpha1)(input)?;
     Ok((input, Cube { color, amount }))
 }
 // 3 blue, 4 red
-fn round(input: &str) -> IResult<&str, Vec<Cube>> {
-    let (input, cubes) = separated_list1(tag(", "), cube)(input)?;
+fn round(input: &str) -> IResult<&str, Vec<Cube<'_>>> {
+    let (input, cubes) = separated_list1(padded(","), cube)(input)?;
     Ok((input, cubes))
 }
 // Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-fn game(input: &str) -> IResult<&str, Game> {
-    let (input, id) = preceded(tag("Game "), digit1)(input)?;
-    let (input, rounds) = preceded(tag(": "), separated_list1(tag("; "), round))(input)?;
+fn game(input: &str) -> IResult<&str, Game<'_>> {
+    let (input, (id, rounds)) =
+        key_value(labelled("Game"), separated_list1(padded(";"), round))(input)?;
     Ok((input, Game { rounds, id }))
 }
-fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
-    let (input, games) = separated_list1(line_ending, game)(input)?;
+#[tracing::instrument(skip_all)]
+fn parse_games(input: &str) -> IResult<&str, Vec<Game<'_>>> {
+    let (input, games) = lines(game)(input)?;
     Ok((input, games))
 }
 
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let map = BTreeMap::from([("red", 12), ("green", 13), ("blue", 14)]);
-    let games = parse_games(input).expect("should parse");
+#[tracing::instrument(skip_all)]
+pub fn process(input: &str) -> miette::Result<Answer, AocError> {
+    process_with(input, &CubeSet::default())
+}
 
-    Ok(games
-        .1
-        .iter()
-        .filter_map(|game| game.valid_for_cube_set(&map))
-        .sum::<u32>()
-        .to_string())
+// Which games could have been played with some other bag
+pub fn process_with(input: &str, bag: &CubeSet) -> miette::Result<Answer, AocError> {
+    tracing::debug!(%bag);
+    let games = parse_all(input, parse_games)?;
+
+    let _solve = tracing::info_span!("solve").entered();
+    Ok(checked_sum(games.iter().filter_map(|game| game.valid_for_cube_set(bag)))?.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use rstest::rstest;
+
     #[test]
     fn test_process() -> miette::Result<()> {
         // todo!("haven't built test yet");
@@ -83,7 +92,43 @@ Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!("8", process(input)?);
+        assert_eq!(Answer::Unsigned(8), process(input)?);
+        Ok(())
+    }
+
+    #[rstest]
+    // enough red and blue for games 3 and 4 as well
+    #[case("20 red, 13 green, 15 blue", "15")]
+    // every game shows some blue
+    #[case("12 red, 13 green", "0")]
+    #[case("12 red, 13 green, 14 blue, 5 purple", "8")]
+    fn what_if_the_bag_held(#[case] bag: &str, #[case] expected: Answer) -> miette::Result<()> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        assert_eq!(expected, process_with(input, &CubeSet::parse(bag)?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_colors_are_not_in_the_bag() -> miette::Result<()> {
+        let input = "Game 1: 3 blue, 2 purple
+Game 2: 1 red; 0 purple";
+        assert_eq!(Answer::Unsigned(2), process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn malformed_game_is_not_skipped() {
+        // game 2 is missing its colon, which used to end the game list early
+        let input = "Game 1: 3 blue, 4 red
+Game 2 1 blue, 2 green
+Game 3: 1 red";
+        assert!(matches!(
+            process(input),
+            Err(AocError::TrailingInput { line: 2, .. })
+        ));
+    }
 }
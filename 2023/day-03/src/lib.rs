@@ -1,4 +1,4 @@
-pub mod custom_error;
+pub use aoc_common::custom_error;
 
 pub mod part1;
 pub mod part2;
@@ -1,9 +1,13 @@
+use aoc_common::{heap, input::read_input, telemetry};
 use day_04::part1::process;
 use miette::Context;
 
 fn main() -> miette::Result<()> {
-    let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    telemetry::init();
+    let _heap = heap::profile(env!("CARGO_PKG_NAME"));
+
+    let file = read_input(env!("CARGO_PKG_NAME")).context("read input")?;
+    let result = process(&file).context("process part 1")?;
     println!("{}", result);
     Ok(())
 }
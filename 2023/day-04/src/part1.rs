@@ -1,12 +1,10 @@
-use crate::custom_error::AocError;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, multispace0, multispace1},
-    combinator::map_res,
-    multi::{many1, separated_list1},
-    sequence::tuple,
-    IResult,
+use crate::{custom_error::AocError, validate::check_cards};
+use aoc_common::{
+    answer::checked_sum,
+    parse::{key_value, labelled, lines, padded, parse_all, unsigned_list},
+    Answer,
 };
+use nom::{sequence::separated_pair, IResult};
 
 #[derive(Debug)]
 struct Card {
@@ -24,9 +22,11 @@ impl Card {
 
     fn calculate_points(&self) -> u32 {
         let my_winners = self.find_winning_numbers().len();
-        (my_winners > 0)
-            .then(|| 2u32.pow(my_winners as u32 - 1))
-            .unwrap_or(0)
+        if my_winners > 0 {
+            2u32.pow(my_winners as u32 - 1)
+        } else {
+            0
+        }
         // match my_winners {
         //     0 => 0,
         //     _ => 2u32.pow(my_winners as u32 - 1),
@@ -34,48 +34,12 @@ impl Card {
     }
 }
 
-fn parse_card_number(input: &str) -> IResult<&str, u32> {
-    // dbg!(input);
-    map_res(digit1, str::parse::<u32>)(input)
-}
-
-fn parse_card_numbers(input: &str) -> IResult<&str, Vec<u32>> {
-    // dbg!(&input);
-    separated_list1(multispace1, parse_card_number)(input)
-}
-
 fn card(input: &str) -> IResult<&str, Card> {
-    // // dbg!(&input);
-    // let (input, _) = tuple((
-    //     multispace0,
-    //     tag("Card"),
-    //     multispace1,
-    //     digit1,
-    //     tag(":"),
-    //     multispace1,
-    // ))(input.trim())?;
-    // // dbg!(&input);
-    // let (input, winning_numbers) = parse_card_numbers(input)?;
-    // // dbg!(&input);
-    // let (input, _) = tuple((multispace1, tag("|"), multispace1))(input)?;
-    // // dbg!(&input);
-    // let (input, my_numbers) = parse_card_numbers(input)?;
-    // // dbg!(&input);
-
-    // dbg!(&input);
-    let (input, (_, winning_numbers, _, my_numbers)) = tuple((
-        tuple((
-            multispace0,
-            tag("Card"),
-            multispace1,
-            digit1,
-            tag(":"),
-            multispace1,
-        )),
-        parse_card_numbers,
-        tuple((multispace1, tag("|"), multispace1)),
-        parse_card_numbers,
-    ))(input)?;
+    let (input, (_, (winning_numbers, my_numbers))) = key_value(
+        labelled::<u32>("Card"),
+        separated_pair(unsigned_list, padded("|"), unsigned_list),
+    )(input)?;
+    tracing::trace!(?winning_numbers, ?my_numbers, "card");
 
     Ok((
         input,
@@ -86,18 +50,27 @@ fn card(input: &str) -> IResult<&str, Card> {
     ))
 }
 
+#[tracing::instrument(skip_all)]
 fn parse_cards(input: &str) -> IResult<&str, Vec<Card>> {
-    many1(card)(input)
+    lines(card)(input)
 }
 
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, cards) = parse_cards(input).expect("this should parse");
-    dbg!(&cards.len());
-    Ok(cards
-        .iter()
-        .map(|card| card.calculate_points())
-        .sum::<u32>()
-        .to_string())
+#[tracing::instrument(skip_all)]
+pub fn process(input: &str) -> miette::Result<Answer, AocError> {
+    check_cards(input)?;
+    let cards = parse_all(input, parse_cards)?;
+    tracing::debug!(cards = cards.len(), "parsed cards");
+    let _solve = tracing::info_span!("solve").entered();
+    Ok(checked_sum(cards.iter().enumerate().map(|(index, card)| {
+        let points = card.calculate_points();
+        tracing::debug!(
+            card = index + 1,
+            wins = card.find_winning_numbers().len(),
+            points
+        );
+        points
+    }))?
+    .into())
 }
 
 #[cfg(test)]
@@ -130,8 +103,23 @@ Card   7: 16 87 54 73 30 43 95 74 50  9 |  9 79 13 71 11 87 82 78 50 12  1 18 24
 Card   8: 48 72 63 92 40 70 53 84 37 31 | 72 40 37 60 82 70  7 88 65  6 92 83 56 48 46 33 31 53 14 34 26 63 61 84 91",
         "1864"
     )]
-    fn tests(#[case] input: &str, #[case] expected: String) -> miette::Result<()> {
+    fn tests(#[case] input: &str, #[case] expected: Answer) -> miette::Result<()> {
         assert_eq!(expected, process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn card_with_a_typo_is_not_skipped() {
+        let input = "Card 1: 41 48 | 83 86
+Card 2: 13 32 | 61 3O
+Card 3:  1 21 | 69 82";
+        assert!(matches!(
+            process(input),
+            Err(AocError::TrailingInput {
+                line: 2,
+                column: 21,
+                ..
+            })
+        ));
+    }
 }
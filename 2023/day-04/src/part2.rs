@@ -1,12 +1,10 @@
-use crate::custom_error::AocError;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, multispace0, multispace1},
-    combinator::map_res,
-    multi::{many1, separated_list1},
-    sequence::tuple,
-    IResult,
+use crate::{custom_error::AocError, validate::check_cards};
+use aoc_common::{
+    answer::checked_sum,
+    parse::{key_value, labelled, lines, padded, parse_all, unsigned_list},
+    Answer,
 };
+use nom::{sequence::separated_pair, IResult};
 use std::collections::BTreeMap;
 
 #[derive(Debug)]
@@ -26,9 +24,11 @@ impl Card {
     #[allow(dead_code)]
     fn calculate_points(&self) -> u32 {
         let my_winners = self.find_winning_numbers().len();
-        (my_winners > 0)
-            .then(|| 2u32.pow(my_winners as u32 - 1))
-            .unwrap_or(0)
+        if my_winners > 0 {
+            2u32.pow(my_winners as u32 - 1)
+        } else {
+            0
+        }
     }
 
     fn cards_won(&self) -> usize {
@@ -36,28 +36,11 @@ impl Card {
     }
 }
 
-fn parse_card_number(input: &str) -> IResult<&str, u32> {
-    map_res(digit1, str::parse::<u32>)(input)
-}
-
-fn parse_card_numbers(input: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(multispace1, parse_card_number)(input)
-}
-
 fn card(input: &str) -> IResult<&str, Card> {
-    let (input, (_, winning_numbers, _, my_numbers)) = tuple((
-        tuple((
-            multispace0,
-            tag("Card"),
-            multispace1,
-            map_res(digit1, str::parse::<u32>),
-            tag(":"),
-            multispace1,
-        )),
-        parse_card_numbers,
-        tuple((multispace1, tag("|"), multispace1)),
-        parse_card_numbers,
-    ))(input)?;
+    let (input, (_, (winning_numbers, my_numbers))) = key_value(
+        labelled::<u32>("Card"),
+        separated_pair(unsigned_list, padded("|"), unsigned_list),
+    )(input)?;
 
     Ok((
         input,
@@ -68,37 +51,40 @@ fn card(input: &str) -> IResult<&str, Card> {
     ))
 }
 
+#[tracing::instrument(skip_all)]
 fn parse_cards(input: &str) -> IResult<&str, Vec<Card>> {
-    many1(card)(input)
+    lines(card)(input)
 }
 
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, cards) = parse_cards(input).expect("the parser to succeed");
-    // dbg!(&cards);
+#[tracing::instrument(skip_all)]
+pub fn process(input: &str) -> miette::Result<Answer, AocError> {
+    check_cards(input)?;
+    let cards = parse_all(input, parse_cards)?;
+    tracing::debug!(cards = cards.len(), "parsed cards");
 
     let count_of_cards_won = (0..cards.len())
         .map(|index| (index, 1))
-        .collect::<BTreeMap<usize, u32>>();
-    // dbg!(&count_of_cards_won);
+        .collect::<BTreeMap<usize, u64>>();
 
+    let _cascade = tracing::info_span!("card_cascade", cards = cards.len()).entered();
     let total_cards_won = cards
         .iter()
         .map(|card| card.cards_won())
         .enumerate()
-        .fold(count_of_cards_won, |mut acc, (index, card_score)| {
+        .try_fold(count_of_cards_won, |mut acc, (index, card_score)| {
             let to_add = *acc.get(&index).unwrap();
+            tracing::debug!(card = index + 1, wins = card_score, copies = to_add);
 
             for i in (index + 1)..(index + 1 + card_score) {
-                acc.entry(i).and_modify(|value| {
-                    *value += to_add;
-                });
+                if let Some(value) = acc.get_mut(&i) {
+                    *value = value.checked_add(to_add).ok_or(AocError::Overflow)?;
+                }
             }
-            acc
-        })
-        .values()
-        .sum::<u32>();
+            Ok::<_, AocError>(acc)
+        })?
+        .into_values();
 
-    Ok(total_cards_won.to_string())
+    Ok(checked_sum(total_cards_won)?.into())
 }
 
 #[cfg(test)]
@@ -117,7 +103,7 @@ Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
         "30"
     )]
-    fn tests(#[case] input: &str, #[case] expected: String) -> miette::Result<()> {
+    fn tests(#[case] input: &str, #[case] expected: Answer) -> miette::Result<()> {
         assert_eq!(expected, process(input)?);
         Ok(())
     }
@@ -0,0 +1,149 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use aoc_common::{report::line_spans, AocError, InputReport};
+
+#[derive(Debug)]
+struct CardShape {
+    winning: Range<usize>,
+    mine: Range<usize>,
+    counts: (usize, usize),
+}
+
+// Shrink a byte range so the label sits under the numbers, not the padding
+fn trim_span(input: &str, span: Range<usize>) -> Range<usize> {
+    let text = &input[span.clone()];
+    let start = span.start + (text.len() - text.trim_start().len());
+    start..start + text.trim().len()
+}
+
+// Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+fn card_shape(input: &str, span: Range<usize>) -> Option<CardShape> {
+    let line = &input[span.clone()];
+    let colon = line.find(':')?;
+    let bar = line.find('|')?;
+    let winning = trim_span(input, span.start + colon + 1..span.start + bar);
+    let mine = trim_span(input, span.start + bar + 1..span.end);
+    let counts = (
+        input[winning.clone()].split_whitespace().count(),
+        input[mine.clone()].split_whitespace().count(),
+    );
+    Some(CardShape {
+        winning,
+        mine,
+        counts,
+    })
+}
+
+// Every card should have the same amount of winning numbers and numbers you have,
+// lines the parser can't make sense of at all are left for it to report
+#[tracing::instrument(skip_all)]
+pub fn check_cards(input: &str) -> Result<(), AocError> {
+    let shapes: Vec<CardShape> = line_spans(input)
+        .filter_map(|(_, span)| card_shape(input, span))
+        .collect();
+
+    let mut tally = BTreeMap::new();
+    for shape in &shapes {
+        *tally.entry(shape.counts).or_insert(0) += 1;
+    }
+    // the most common shape, on a tie the one that turns up first
+    let Some(most) = tally.values().max() else {
+        return Ok(());
+    };
+    let Some((winning, mine)) = shapes
+        .iter()
+        .map(|shape| shape.counts)
+        .find(|counts| tally[counts] == *most)
+    else {
+        return Ok(());
+    };
+
+    let report = shapes
+        .iter()
+        .filter(|shape| shape.counts != (winning, mine))
+        .fold(
+            InputReport::new("card has a different number count than the rest", input),
+            |report, shape| {
+                let report = if shape.counts.0 != winning {
+                    report.with_label(
+                        shape.winning.clone(),
+                        format!("{} winning numbers, expected {winning}", shape.counts.0),
+                    )
+                } else {
+                    report
+                };
+                if shape.counts.1 != mine {
+                    report.with_label(
+                        shape.mine.clone(),
+                        format!("{} numbers you have, expected {mine}", shape.counts.1),
+                    )
+                } else {
+                    report
+                }
+            },
+        )
+        .with_note(format!(
+            "most cards have {winning} winning numbers and {mine} numbers you have"
+        ));
+
+    if report.labels.is_empty() {
+        Ok(())
+    } else {
+        Err(report.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_every_mismatched_card() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54";
+
+        let Err(AocError::InvalidInput(report)) = check_cards(input) else {
+            panic!("expected the mismatched cards to be reported");
+        };
+        let labelled: Vec<(&str, &str)> = report
+            .labels
+            .iter()
+            .map(|(span, message)| (&input[span.clone()], message.as_str()))
+            .collect();
+        assert_eq!(
+            labelled,
+            vec![
+                ("13 32 20 16", "4 winning numbers, expected 5"),
+                ("59 84 76 51 58  5 54", "7 numbers you have, expected 8"),
+            ]
+        );
+    }
+
+    #[test]
+    fn ties_go_to_the_first_card() {
+        let input = "Card 1: 41 48 | 83 86  6
+Card 2: 13 32 20 | 61 30
+Card 3: 1 2 3 | 4 5
+Card 4: 7 8 | 9 10 11";
+
+        let Err(AocError::InvalidInput(report)) = check_cards(input) else {
+            panic!("expected the cards unlike card 1 to be reported");
+        };
+        let labelled: Vec<&str> = report
+            .labels
+            .iter()
+            .map(|(span, _)| &input[span.clone()])
+            .collect();
+        assert_eq!(labelled, vec!["13 32 20", "61 30", "1 2 3", "4 5"]);
+    }
+
+    #[test]
+    fn accepts_consistent_cards() {
+        let input = "Card 1: 41 48 | 83 86  6
+Card 2: 13 32 | 61 30 68
+";
+        assert!(check_cards(input).is_ok());
+    }
+}
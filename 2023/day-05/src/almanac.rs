@@ -0,0 +1,192 @@
+use std::ops::Range;
+
+use aoc_common::{
+    parse::{blank_lines, blocks, key_value, lines, unsigned, unsigned_list},
+    report::line_spans,
+    AocError, InputReport,
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending, space1},
+    sequence::{preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+
+// One line of a map block: `destination source length`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap {
+    pub destination: u64,
+    pub source: u64,
+    pub length: u64,
+}
+
+impl RangeMap {
+    pub fn source_end(&self) -> Result<u64, AocError> {
+        self.source
+            .checked_add(self.length)
+            .ok_or(AocError::Overflow)
+    }
+
+    // without working out the end, so a range reaching past u64::MAX can't overflow
+    pub fn contains(&self, value: u64) -> bool {
+        value >= self.source && value - self.source < self.length
+    }
+
+    // Where a source value ends up, it has to be at least `source`
+    fn shift(&self, value: u64) -> Result<u64, AocError> {
+        self.destination
+            .checked_add(value - self.source)
+            .ok_or(AocError::Overflow)
+    }
+
+    // Some(destination) when the value falls inside the source range
+    pub fn lookup(&self, value: u64) -> Option<Result<u64, AocError>> {
+        self.contains(value).then(|| self.shift(value))
+    }
+}
+
+// A full `X-to-Y map:` block
+#[derive(Debug)]
+pub struct CategoryMap<'a> {
+    pub source: &'a str,
+    pub destination: &'a str,
+    pub ranges: Vec<RangeMap>,
+}
+
+impl<'a> CategoryMap<'a> {
+    // Any source numbers that aren't mapped correspond to the same destination number
+    pub fn lookup(&self, value: u64) -> Result<u64, AocError> {
+        self.ranges
+            .iter()
+            .find_map(|range| range.lookup(value))
+            .unwrap_or(Ok(value))
+    }
+
+    // Split the interval on every range boundary it crosses and shift the
+    // covered pieces, whatever is left over passes through unchanged
+    pub fn lookup_range(&self, interval: Range<u64>) -> Result<Vec<Range<u64>>, AocError> {
+        let mut mapped = Vec::new();
+        let mut unmapped = vec![interval];
+
+        for range in &self.ranges {
+            let (source, source_end) = (range.source, range.source_end()?);
+            let mut left = Vec::new();
+            for interval in unmapped {
+                let overlap = interval.start.max(source)..interval.end.min(source_end);
+                if !overlap.is_empty() {
+                    mapped.push(range.shift(overlap.start)?..range.shift(overlap.end)?);
+                }
+
+                let before = interval.start..interval.end.min(source);
+                let after = interval.start.max(source_end)..interval.end;
+                left.extend(
+                    [before, after]
+                        .into_iter()
+                        .filter(|piece| !piece.is_empty()),
+                );
+            }
+            unmapped = left;
+        }
+
+        mapped.extend(unmapped);
+        Ok(mapped)
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac<'a> {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<CategoryMap<'a>>,
+}
+
+impl<'a> Almanac<'a> {
+    // Chain a seed through every map, seed -> soil -> ... -> location
+    pub fn location(&self, seed: u64) -> Result<u64, AocError> {
+        self.maps
+            .iter()
+            .try_fold(seed, |value, map| map.lookup(value))
+    }
+
+    // Part 2 reads the seeds line as (start, length) pairs. A range of
+    // length 0 has no seeds in it and is left out.
+    pub fn seed_ranges(&self, input: &str) -> Result<Vec<Range<u64>>, AocError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(seeds_report(
+                input,
+                "seeds don't pair up into ranges",
+                format!("{} numbers, the last start has no length", self.seeds.len()),
+            ));
+        }
+        self.seeds
+            .chunks_exact(2)
+            .filter(|pair| pair[1] > 0)
+            .map(|pair| {
+                let end = pair[0].checked_add(pair[1]).ok_or(AocError::Overflow)?;
+                Ok(pair[0]..end)
+            })
+            .collect()
+    }
+
+    // Push whole intervals through every map instead of single seeds
+    pub fn location_ranges(&self, seeds: Range<u64>) -> Result<Vec<Range<u64>>, AocError> {
+        self.maps.iter().try_fold(vec![seeds], |intervals, map| {
+            let mut next = Vec::new();
+            for interval in intervals {
+                next.extend(map.lookup_range(interval)?);
+            }
+            Ok(next)
+        })
+    }
+}
+
+// An error pointing at the seeds line, which is always the first
+pub fn seeds_report(input: &str, message: &str, label: impl Into<String>) -> AocError {
+    let (_, line) = line_spans(input).next().unwrap_or((0, 0..0));
+    InputReport::new(message, input)
+        .with_label(line, label)
+        .into()
+}
+
+// 50 98 2
+fn range_map(input: &str) -> IResult<&str, RangeMap> {
+    let (input, (destination, _, source, _, length)) =
+        tuple((unsigned, space1, unsigned, space1, unsigned))(input)?;
+    Ok((
+        input,
+        RangeMap {
+            destination,
+            source,
+            length,
+        },
+    ))
+}
+
+// seed-to-soil map:
+// 50 98 2
+// 52 50 48
+fn category_map(input: &str) -> IResult<&str, CategoryMap<'_>> {
+    let (input, ((source, destination), ranges)) = key_value(
+        terminated(separated_pair(alpha1, tag("-to-"), alpha1), tag(" map")),
+        preceded(line_ending, lines(range_map)),
+    )(input)?;
+    Ok((
+        input,
+        CategoryMap {
+            source,
+            destination,
+            ranges,
+        },
+    ))
+}
+
+// seeds: 79 14 55 13
+fn seeds(input: &str) -> IResult<&str, Vec<u64>> {
+    let (input, (_, seeds)) = key_value(tag("seeds"), unsigned_list)(input)?;
+    Ok((input, seeds))
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse_almanac(input: &str) -> IResult<&str, Almanac<'_>> {
+    let (input, (seeds, maps)) = separated_pair(seeds, blank_lines, blocks(category_map))(input)?;
+    Ok((input, Almanac { seeds, maps }))
+}
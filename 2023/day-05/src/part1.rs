@@ -1,11 +1,20 @@
-use crate::custom_error::AocError;
+use crate::{almanac::parse_almanac, custom_error::AocError};
+use aoc_common::{parse::parse_all, Answer};
 
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    // parse input
-    // create mappings between different maps
-    // get lowest value for location
+#[tracing::instrument(skip_all)]
+pub fn process(input: &str) -> miette::Result<Answer, AocError> {
+    let almanac = parse_all(input, parse_almanac)?;
 
-    Ok("todo - part 1".to_string())
+    let _solve = tracing::info_span!("solve").entered();
+    let mut locations = Vec::new();
+    for &seed in &almanac.seeds {
+        let location = almanac.location(seed)?;
+        tracing::debug!(seed, location);
+        locations.push(location);
+    }
+    let lowest_location = locations.into_iter().min().expect("at least one seed");
+
+    Ok(lowest_location.into())
 }
 
 #[cfg(test)]
@@ -52,8 +61,17 @@ humidity-to-location map:
         "35"
     )]
 
-    fn tests(#[case] input: &str, #[case] expected: String) -> miette::Result<()> {
+    fn tests(#[case] input: &str, #[case] expected: Answer) -> miette::Result<()> {
         assert_eq!(expected, process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn overflowing_destination_is_an_error() {
+        let input = "seeds: 5
+
+seed-to-location map:
+18446744073709551615 0 10";
+        assert!(matches!(process(input), Err(AocError::Overflow)));
+    }
 }
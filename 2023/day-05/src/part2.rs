@@ -1,7 +1,25 @@
-use crate::custom_error::AocError;
+use crate::{
+    almanac::{parse_almanac, seeds_report},
+    custom_error::AocError,
+};
+use aoc_common::{parse::parse_all, Answer};
 
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    Ok("todo - part 2".to_string())
+#[tracing::instrument(skip_all)]
+pub fn process(input: &str) -> miette::Result<Answer, AocError> {
+    let almanac = parse_all(input, parse_almanac)?;
+
+    let _solve = tracing::info_span!("solve").entered();
+    let mut locations = Vec::new();
+    for seeds in almanac.seed_ranges(input)? {
+        locations.extend(almanac.location_ranges(seeds)?);
+    }
+    let lowest_location = locations
+        .iter()
+        .map(|location| location.start)
+        .min()
+        .ok_or_else(|| seeds_report(input, "no seeds to plant", "every seed range is empty"))?;
+
+    Ok(lowest_location.into())
 }
 
 #[cfg(test)]
@@ -11,10 +29,114 @@ mod tests {
     use rstest::rstest;
 
     #[rstest]
-    #[case("", "")]
+    #[case(
+        "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4",
+        "46"
+    )]
+    // unmapped: nothing covers 10..15 so it passes straight through
+    #[case(
+        "seeds: 10 5
+
+seed-to-location map:
+100 50 10",
+        "10"
+    )]
+    // overlapping: 45..55 only partly overlaps 40..48, 45..48 shifts to 15..18
+    #[case(
+        "seeds: 45 10
+
+seed-to-location map:
+10 40 8",
+        "15"
+    )]
+    // adjacent: 48..52 straddles the boundary between 0..50 and 50..100
+    #[case(
+        "seeds: 48 4
+
+seed-to-location map:
+20 0 50
+0 50 50",
+        "0"
+    )]
+    // split pieces keep going through the next layer
+    #[case(
+        "seeds: 0 10 90 5
 
-    fn tests(#[case] input: &str, #[case] expected: String) -> miette::Result<()> {
+seed-to-soil map:
+50 0 5
+
+soil-to-location map:
+200 50 3
+1 90 5",
+        "1"
+    )]
+
+    fn tests(#[case] input: &str, #[case] expected: Answer) -> miette::Result<()> {
         assert_eq!(expected, process(input)?);
         Ok(())
     }
+
+    // an empty range is skipped, the other one still counts
+    #[test]
+    fn empty_seed_ranges_are_skipped() -> miette::Result<()> {
+        let input = "seeds: 5 0 20 3
+
+seed-to-location map:
+100 20 1";
+        assert_eq!(Answer::Unsigned(21), process(input)?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("seeds: 79", "seeds don't pair up into ranges")]
+    #[case("seeds: 1 2 3", "seeds don't pair up into ranges")]
+    #[case("seeds: 5 0", "no seeds to plant")]
+    fn bad_seed_ranges_point_at_the_seeds(#[case] seeds: &str, #[case] message: &str) {
+        let input = format!("{seeds}\n\nseed-to-location map:\n100 20 1");
+        let Err(AocError::InvalidInput(report)) = process(&input) else {
+            panic!("expected the seeds line to be reported");
+        };
+        assert_eq!(report.message, message);
+        assert_eq!(report.labels[0].0, 0..seeds.len());
+    }
+
+    #[rstest]
+    #[case("seeds: 18446744073709551615 5\n\nseed-to-location map:\n100 20 1")]
+    #[case("seeds: 1 2\n\nseed-to-location map:\n100 18446744073709551615 5")]
+    #[case("seeds: 1 2\n\nseed-to-location map:\n18446744073709551615 0 5")]
+    fn overflowing_ranges_are_an_error(#[case] input: &str) {
+        assert!(matches!(process(input), Err(AocError::Overflow)));
+    }
 }